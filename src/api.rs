@@ -0,0 +1,347 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// How long a request waits for the GUI thread to answer.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The optional HTTP/JSON API on 127.0.0.1. Every request must carry the
+/// token from the secrets file as `Authorization: Bearer <token>` or
+/// `X-LoVA-Token: <token>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+        }
+    }
+}
+
+/// 32 hex digits from the operating system's random number generator.
+pub fn generate_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// What an API call asks of the app; answered on the GUI thread.
+#[derive(Debug)]
+pub enum ApiRequest {
+    ListJobs,
+    GetJob(u64),
+    Enqueue { url: String, preset: Option<String> },
+    Cancel(u64),
+    ListPresets,
+}
+
+/// A JSON body, or an HTTP status with an error message.
+pub type ApiReply = Result<Value, (u16, String)>;
+
+pub struct PendingRequest {
+    pub request: ApiRequest,
+    pub reply: Sender<ApiReply>,
+}
+
+/// Stops the listener thread when dropped and waits until the port is
+/// released, so a restart can bind it again right away.
+pub struct ApiServer {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Binds to 127.0.0.1 and serves requests until the returned handle is dropped.
+/// `ctx` is woken for each request so the app answers even when idle.
+pub fn spawn_server(
+    config: &ApiConfig,
+    token: String,
+    requests: Sender<PendingRequest>,
+    ctx: egui::Context,
+) -> io::Result<ApiServer> {
+    let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, config.port)))?;
+    listener.set_nonblocking(true)?;
+    let running = Arc::new(AtomicBool::new(true));
+    let port = config.port;
+
+    let flag = running.clone();
+    let thread = std::thread::spawn(move || {
+        while flag.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let requests = requests.clone();
+                    let token = token.clone();
+                    let ctx = ctx.clone();
+                    std::thread::spawn(move || {
+                        let _ = handle_connection(stream, port, &token, &requests, &ctx);
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => std::thread::sleep(Duration::from_millis(100)),
+                Err(_) => std::thread::sleep(Duration::from_millis(100)),
+            }
+        }
+    });
+
+    Ok(ApiServer { running, thread: Some(thread) })
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    port: u16,
+    token: &str,
+    requests: &Sender<PendingRequest>,
+    ctx: &egui::Context,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut writer = stream.try_clone()?;
+
+    let reply = match read_request(stream) {
+        Ok(request) if request.method == "OPTIONS" => Ok(Value::Null),
+        Ok(request) => authorize(&request, port, token).and_then(|()| route(&request)).and_then(|api_request| {
+            let (reply_tx, reply_rx) = mpsc::channel();
+            requests
+                .send(PendingRequest { request: api_request, reply: reply_tx })
+                .map_err(|_| (503, "app is shutting down".to_string()))?;
+            ctx.request_repaint();
+            reply_rx
+                .recv_timeout(REPLY_TIMEOUT)
+                .map_err(|_| (503, "app did not answer in time".to_string()))?
+        }),
+        Err(e) => Err((400, e.to_string())),
+    };
+
+    let (status, body) = match reply {
+        Ok(Value::Null) => (204, String::new()),
+        Ok(value) => (200, value.to_string()),
+        Err((status, message)) => (status, json!({ "error": message }).to_string()),
+    };
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, DELETE, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Authorization, Content-Type, X-LoVA-Token\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    )?;
+    writer.flush()
+}
+
+fn read_request(stream: TcpStream) -> io::Result<HttpRequest> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or_else(|| invalid("empty request"))?.to_string();
+    let path = parts.next().ok_or_else(|| invalid("missing path"))?.to_string();
+
+    let mut headers = Vec::new();
+    let mut header_bytes = line.len();
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+        header_bytes += read;
+        if read == 0 || header_bytes > MAX_HEADER_BYTES {
+            return Err(invalid("headers too large or incomplete"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = HttpRequest { method, path, headers, body: Vec::new() };
+    let length: usize = request.header("Content-Length").and_then(|value| value.parse().ok()).unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Err(invalid("body too large"));
+    }
+    request.body = vec![0; length];
+    reader.read_exact(&mut request.body)?;
+    Ok(request)
+}
+
+/// Checks the token, and the Host header against DNS rebinding from web pages.
+fn authorize(request: &HttpRequest, port: u16, token: &str) -> Result<(), (u16, String)> {
+    let host = request.header("Host").unwrap_or_default();
+    let local_hosts = [format!("127.0.0.1:{}", port), format!("localhost:{}", port)];
+    if !local_hosts.iter().any(|local| local.eq_ignore_ascii_case(host)) {
+        return Err((403, "unexpected Host header".to_string()));
+    }
+
+    let given = request
+        .header("X-LoVA-Token")
+        .or_else(|| request.header("Authorization").and_then(|value| value.strip_prefix("Bearer ")))
+        .unwrap_or_default();
+    if token.is_empty() || given.trim() != token {
+        return Err((401, "missing or wrong token".to_string()));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct EnqueueBody {
+    url: String,
+    #[serde(default)]
+    preset: Option<String>,
+}
+
+fn route(request: &HttpRequest) -> Result<ApiRequest, (u16, String)> {
+    let path = request.path.split('?').next().unwrap_or_default().trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+    let job_id = |id: &str| id.parse::<u64>().map_err(|_| (400, format!("invalid job id: {}", id)));
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "jobs"]) => Ok(ApiRequest::ListJobs),
+        ("GET", ["api", "jobs", id]) => Ok(ApiRequest::GetJob(job_id(id)?)),
+        ("POST", ["api", "jobs"]) => {
+            let body: EnqueueBody = serde_json::from_slice(&request.body)
+                .map_err(|e| (400, format!("invalid body: {}", e)))?;
+            Ok(ApiRequest::Enqueue { url: body.url, preset: body.preset })
+        }
+        ("DELETE", ["api", "jobs", id]) | ("POST", ["api", "jobs", id, "cancel"]) => Ok(ApiRequest::Cancel(job_id(id)?)),
+        ("GET", ["api", "presets"]) => Ok(ApiRequest::ListPresets),
+        _ => Err((404, format!("no route for {} {}", request.method, request.path))),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0123456789abcdef";
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> HttpRequest {
+        HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn status(result: Result<(), (u16, String)>) -> Option<u16> {
+        result.err().map(|(status, _)| status)
+    }
+
+    #[test]
+    fn generates_distinct_hex_tokens() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(token.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token().unwrap());
+    }
+
+    #[test]
+    fn accepts_token_from_either_header() {
+        let bearer = request("GET", "/api/jobs", &[("Host", "127.0.0.1:8765"), ("Authorization", "Bearer 0123456789abcdef")], "");
+        assert_eq!(status(authorize(&bearer, 8765, TOKEN)), None);
+        let custom = request("GET", "/api/jobs", &[("host", "LOCALHOST:8765"), ("x-lova-token", TOKEN)], "");
+        assert_eq!(status(authorize(&custom, 8765, TOKEN)), None);
+    }
+
+    #[test]
+    fn rejects_foreign_or_missing_host() {
+        for host in ["evil.example:8765", "127.0.0.1:9999", "127.0.0.1", "localhost.evil.example:8765"] {
+            let request = request("GET", "/api/jobs", &[("Host", host), ("X-LoVA-Token", TOKEN)], "");
+            assert_eq!(status(authorize(&request, 8765, TOKEN)), Some(403), "{}", host);
+        }
+        let request = request("GET", "/api/jobs", &[("X-LoVA-Token", TOKEN)], "");
+        assert_eq!(status(authorize(&request, 8765, TOKEN)), Some(403));
+    }
+
+    #[test]
+    fn rejects_wrong_missing_or_unset_token() {
+        let wrong = request("GET", "/api/jobs", &[("Host", "127.0.0.1:8765"), ("X-LoVA-Token", "nope")], "");
+        assert_eq!(status(authorize(&wrong, 8765, TOKEN)), Some(401));
+        let basic = request("GET", "/api/jobs", &[("Host", "127.0.0.1:8765"), ("Authorization", "Basic 0123456789abcdef")], "");
+        assert_eq!(status(authorize(&basic, 8765, TOKEN)), Some(401));
+        let missing = request("GET", "/api/jobs", &[("Host", "127.0.0.1:8765")], "");
+        assert_eq!(status(authorize(&missing, 8765, TOKEN)), Some(401));
+        assert_eq!(status(authorize(&missing, 8765, "")), Some(401));
+    }
+
+    #[test]
+    fn routes_job_requests() {
+        assert!(matches!(route(&request("GET", "/api/jobs/", &[], "")), Ok(ApiRequest::ListJobs)));
+        assert!(matches!(route(&request("GET", "/api/jobs/7?x=1", &[], "")), Ok(ApiRequest::GetJob(7))));
+        assert!(matches!(route(&request("DELETE", "/api/jobs/7", &[], "")), Ok(ApiRequest::Cancel(7))));
+        assert!(matches!(route(&request("POST", "/api/jobs/7/cancel", &[], "")), Ok(ApiRequest::Cancel(7))));
+        assert!(matches!(route(&request("GET", "/api/presets", &[], "")), Ok(ApiRequest::ListPresets)));
+        assert!(matches!(
+            route(&request("POST", "/api/jobs", &[], r#"{"url":"https://example.com/v","preset":"Best"}"#)),
+            Ok(ApiRequest::Enqueue { url, preset: Some(preset) }) if url == "https://example.com/v" && preset == "Best"
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_routes() {
+        assert!(matches!(route(&request("GET", "/api/jobs/abc", &[], "")), Err((400, _))));
+        assert!(matches!(route(&request("POST", "/api/jobs", &[], "{}")), Err((400, _))));
+        assert!(matches!(route(&request("PUT", "/api/jobs", &[], "")), Err((404, _))));
+        assert!(matches!(route(&request("GET", "/", &[], "")), Err((404, _))));
+    }
+}
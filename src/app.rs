@@ -1,7 +1,30 @@
+use crate::api::{self, ApiReply, ApiRequest, ApiServer, PendingRequest};
+use crate::auth::{self, SecretStore};
+use crate::clipboard;
 use crate::config::Config;
+use crate::desktop;
+use crate::ffmpeg;
+use crate::i18n::{self, t, tf, tn};
+use crate::info_cache;
+use crate::instance::InstanceListener;
+use crate::links;
+use crate::notifications::{self, NotificationEvent};
+use crate::presets::Preset;
+use crate::queue::{DownloadedFile, Job, JobQueue, JobState, Progress};
+use crate::scheme;
+use crate::shortcuts::Action;
+use crate::theme::ThemeSettings;
+use crate::ytdlp::{self, UpdateChannel};
+use chrono::Local;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Sender, Receiver};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -13,13 +36,14 @@ pub struct FormatInfo {
     pub acodec: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(untagged)]
-pub enum VideoResponse {
-    Single(SingleVideo),
-    Multiple(Vec<SingleVideo>),
+impl FormatInfo {
+    pub fn is_video_only(&self) -> bool {
+        self.vcodec != "none" && self.acodec.as_deref() == Some("none")
+    }
 }
 
+/// The fields read from one `--dump-json` line; everything else is skipped
+/// while parsing.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SingleVideo {
     #[serde(default)]
@@ -36,155 +60,1129 @@ pub struct SingleVideo {
     pub acodec: Option<String>,
 }
 
+/// Window-level UI state kept in eframe's storage between launches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub show_settings: bool,
+    pub preset: String,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            show_settings: false,
+            preset: Preset::default().name,
+        }
+    }
+}
+
+/// What the status line reports, which decides its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    Info,
+    Success,
+    Error,
+}
+
 pub struct YtDlpApp {
     pub yt_dlp_path: String,
     pub download_dir: String,
     pub url: String,
     pub formats: Vec<FormatInfo>,
+    /// The format list was read from the info cache and can be refreshed.
+    pub formats_cached: bool,
     pub selected_format: Option<usize>,
     pub status: String,
+    pub status_kind: StatusKind,
     pub show_settings: bool,
     pub config: Config,
+    pub yt_dlp_version: Option<String>,
+    /// The binary the last check found; differs from `yt_dlp_path` when the
+    /// configured one is missing and a copy on `PATH` is used instead.
+    pub yt_dlp_resolved: Option<String>,
+    pub yt_dlp_output: String,
+    pub ffmpeg_version: Option<String>,
+    pub ffmpeg_location: Option<String>,
+    pub ffmpeg_error: String,
+    pub merge_audio: bool,
+    pub extract_audio: bool,
+    pub secrets: SecretStore,
+    pub twofactor_code: String,
+    pub last_command: String,
+    pub queue: JobQueue,
+    /// Unfinished jobs were restored; hold the queue until the user decides.
+    pub show_recovery: bool,
+    /// Problems found while loading the config, shown until dismissed.
+    pub config_errors: Vec<String>,
+    /// Name typed into the "save profile" field.
+    pub profile_name: String,
+    /// Theme last pushed into the egui context.
+    pub applied_theme: Option<ThemeSettings>,
+    /// Name of the preset used for downloads that skip the format list.
+    pub preset: String,
+    /// Column widths were dragged; saved once the mouse is released.
+    pub columns_changed: bool,
+    /// Links picked up by the clipboard watcher, waiting to be fetched.
+    pub detected_links: Vec<String>,
+    pub clipboard_error: String,
+    /// Last file a finished download produced, offered for opening.
+    pub last_download: Option<DownloadedFile>,
+    pub show_palette: bool,
+    /// A shortcut or the palette asked to cancel; waits for confirmation.
+    pub confirm_cancel: bool,
+    pub palette_query: String,
+    /// Highlighted entry among the filtered palette actions.
+    pub palette_index: usize,
+    clipboard_watching: Arc<AtomicBool>,
+    clipboard_patterns: clipboard::PatternCache,
     runtime: Runtime,
     tx: Sender<String>,
     rx: Receiver<String>,
     is_fetching: bool,
     is_downloading: bool,
+    egui_ctx: Option<egui::Context>,
+    api_server: Option<ApiServer>,
+    api_tx: Sender<PendingRequest>,
+    api_rx: Receiver<PendingRequest>,
+    /// Set to stop the running download; checked by its task.
+    cancel_download: Arc<AtomicBool>,
+    is_updating: bool,
+    pending_checks: usize,
 }
 
 impl Default for YtDlpApp {
     fn default() -> Self {
         let runtime = Runtime::new().unwrap();
         let (tx, rx) = mpsc::channel();
-        let config = Config::load();
+        let (api_tx, api_rx) = mpsc::channel();
+        let (config, config_errors) = Config::load();
+        i18n::set_language(config.language);
+        let (queue, queue_error) = JobQueue::load();
+        let (status_kind, status) = match queue_error {
+            Some(error) => (StatusKind::Error, format!("❌ {}", error)),
+            None => (StatusKind::Info, String::new()),
+        };
 
-        Self {
+        let mut app = Self {
             yt_dlp_path: config.yt_dlp_path.clone(),
             download_dir: config.download_dir.clone(),
             config,
             url: String::new(),
             formats: Vec::new(),
+            formats_cached: false,
             selected_format: None,
-            status: String::new(),
+            status,
+            status_kind,
             show_settings: false,
+            yt_dlp_version: None,
+            yt_dlp_output: String::new(),
+            ffmpeg_version: None,
+            ffmpeg_location: None,
+            yt_dlp_resolved: None,
+            ffmpeg_error: String::new(),
+            merge_audio: true,
+            extract_audio: false,
+            secrets: SecretStore::load(),
+            twofactor_code: String::new(),
+            last_command: String::new(),
+            show_recovery: queue.has_unfinished(),
+            config_errors,
+            profile_name: String::new(),
+            applied_theme: None,
+            preset: Preset::default().name,
+            columns_changed: false,
+            queue,
+            detected_links: Vec::new(),
+            clipboard_error: String::new(),
+            last_download: None,
+            show_palette: false,
+            confirm_cancel: false,
+            palette_query: String::new(),
+            palette_index: 0,
+            clipboard_watching: Arc::new(AtomicBool::new(false)),
+            clipboard_patterns: clipboard::PatternCache::default(),
             runtime,
             tx,
             rx,
             is_fetching: false,
             is_downloading: false,
-        }
+            egui_ctx: None,
+            api_server: None,
+            api_tx,
+            api_rx,
+            cancel_download: Arc::new(AtomicBool::new(false)),
+            is_updating: false,
+            pending_checks: 0,
+        };
+        app.check_yt_dlp();
+        app.check_ffmpeg();
+        app.set_clipboard_watch(app.config.clipboard.enabled);
+        app
     }
 }
 
 impl YtDlpApp {
+    /// Shows the formats of the URL in the input, from the info cache when
+    /// it has a fresh entry.
     pub fn fetch_formats(&mut self) {
+        self.fetch_formats_from(true);
+    }
+
+    /// Fetches the formats again, replacing the cached info JSON.
+    pub fn refresh_formats(&mut self) {
+        self.fetch_formats_from(false);
+    }
+
+    fn fetch_formats_from(&mut self, use_cache: bool) {
         if self.is_fetching {
             return;
         }
 
+        // Several pasted links go to the queue; a single one is cleaned up first.
+        let found = links::scan_urls(&self.url);
+        if found.len() > 1 {
+            self.open_links(found);
+            return;
+        }
+        let input = found.into_iter().next().unwrap_or_else(|| self.url.clone());
+        match links::normalize(&input) {
+            Ok(url) => self.url = url,
+            Err(e) => {
+                self.set_status(StatusKind::Error, format!("❌ {}", e));
+                return;
+            }
+        }
+
+        let ttl = self.info_cache_ttl();
+        let cache_key = self.info_cache_key(&self.url);
+        if let Some((json, age)) = ttl.filter(|_| use_cache).and_then(|ttl| info_cache::load(&cache_key, ttl)) {
+            if let Ok(formats) = process_video_response(&json) {
+                self.formats = formats;
+                self.selected_format = None;
+                self.formats_cached = true;
+                self.set_status(StatusKind::Success, tn("status.formats_cached", self.formats.len(), &[("minutes", &(age.as_secs() / 60))]));
+                return;
+            }
+        }
+
+        let (mut args, credentials) = match self.base_args(&self.url) {
+            Ok(base) => base,
+            Err(e) => {
+                self.set_status(StatusKind::Error, format!("❌ {}", tf("status.credentials_failed", &[("error", &e)])));
+                return;
+            }
+        };
         self.is_fetching = true;
-        self.status = "⏳ Fetching formats...".to_string();
-        let url = self.url.clone();
-        let yt_dlp_path = self.yt_dlp_path.clone();
+        self.formats.clear();
+        self.formats_cached = false;
+        self.selected_format = None;
+        self.set_status(StatusKind::Info, t("status.fetching").to_string());
+        // One JSON object per line and entry, so playlists can be parsed as they
+        // arrive instead of holding the whole output in memory.
+        args.extend([
+            links::classify(&self.url).yt_dlp_flag().to_string(),
+            "--dump-json".to_string(),
+            self.url.clone(),
+        ]);
+        let yt_dlp_path = self.yt_dlp_binary();
+        self.last_command = auth::redact_command(&yt_dlp_path, &args);
         let tx = self.tx.clone();
 
         self.runtime.spawn(async move {
-            let result = Command::new(&yt_dlp_path)
-                .args(&["--no-check-certificate", "--skip-download", "--print-json", &url])
-                .output();
-
-            match result {
-                Ok(output) => {
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    let formats = process_video_response(&output_str);
-                    match formats {
-                        Ok(formats) => {
-                            let _ = tx.send(format!("FORMATS:{}", serde_json::to_string(&formats).unwrap()));
-                        }
-                        Err(e) => {
-                            let _ = tx.send(format!("ERROR:Error parsing response: {}", e));
-                        }
-                    }
-                }
+            let mut child = match Command::new(&yt_dlp_path)
+                .args(&args)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+            {
+                Ok(child) => child,
                 Err(e) => {
                     let _ = tx.send(format!("ERROR:{}", e));
+                    return;
+                }
+            };
+            // Drained on its own thread so a chatty stderr cannot stall yt-dlp.
+            let errors = child.stderr.take().map(|stderr| std::thread::spawn(move || last_error_line(stderr)));
+
+            let mut entries = 0;
+            // Playlist entries mostly share format IDs; only new ones are sent,
+            // so the list stays as long as the set of distinct formats.
+            let mut seen = HashSet::new();
+            let mut single_entry = None;
+            let mut parse_error = None;
+            if let Some(stdout) = child.stdout.take() {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str::<SingleVideo>(&line) {
+                        Ok(video) => {
+                            entries += 1;
+                            let formats: Vec<FormatInfo> = entry_formats(video)
+                                .into_iter()
+                                .filter(|format| seen.insert(format.format_id.clone()))
+                                .collect();
+                            if !formats.is_empty() {
+                                let _ = tx.send(format!("FORMATS_ENTRY:{}", serde_json::to_string(&formats).unwrap_or_default()));
+                            }
+                            single_entry = (entries == 1).then_some(line);
+                        }
+                        Err(e) => parse_error = Some(e),
+                    }
                 }
             }
+            let _ = child.wait();
+            drop(credentials);
+            let error = errors.and_then(|reader| reader.join().ok()).flatten();
+
+            let msg = if entries > 0 {
+                // Only a single video can be read back with `--load-info-json`.
+                if let (Some(ttl), Some(json)) = (ttl, single_entry) {
+                    let _ = info_cache::store(&cache_key, &json, ttl);
+                }
+                "FORMATS_DONE:".to_string()
+            } else if let Some(error) = error {
+                format!("ERROR:{}", error)
+            } else if let Some(e) = parse_error {
+                format!("ERROR:{}", tf("status.parse_error", &[("error", &e)]))
+            } else {
+                format!("ERROR:{}", t("status.no_formats"))
+            };
+            let _ = tx.send(msg);
         });
     }
 
     pub fn download_selected_format(&mut self) {
-        if self.is_downloading {
+        self.enqueue_selected_format(false);
+    }
+
+    /// Queues the selected format to run once the schedule window opens.
+    pub fn schedule_selected_format(&mut self) {
+        self.enqueue_selected_format(true);
+    }
+
+    /// Two-factor codes expire within minutes, so a job that starts hours
+    /// later cannot use one.
+    pub fn can_schedule(&self) -> bool {
+        !self.auth_profile().is_some_and(|profile| profile.twofactor)
+    }
+
+    fn enqueue_selected_format(&mut self, scheduled: bool) {
+        let Some(format) = self.selected_format.and_then(|index| self.formats.get(index)) else {
+            return;
+        };
+        if scheduled && !self.can_schedule() {
+            self.set_status(StatusKind::Error, format!("❌ {}", t("formats.schedule_twofactor")));
             return;
         }
 
-        if let Some(index) = self.selected_format {
-            if let Some(format) = self.formats.get(index) {
-                self.is_downloading = true;
-                self.status = "⏳ Downloading...".to_string();
-                let url = self.url.clone();
-                let yt_dlp_path = self.yt_dlp_path.clone();
-                let download_dir = self.download_dir.clone();
-                let format_id = format.format_id.clone();
-                let tx = self.tx.clone();
-
-                self.runtime.spawn(async move {
-                    let result = Command::new(&yt_dlp_path)
-                        .args(&[
-                            "--no-check-certificate",
-                            "-f",
-                            &format_id,
-                            "-o",
-                            &format!("{}/%(title)s.%(ext)s", download_dir),
-                            &url,
-                        ])
-                        .status();
-
-                    match result {
-                        Ok(status) => {
-                            let msg = if status.success() {
-                                "SUCCESS:Download completed!"
-                            } else {
-                                "ERROR:Download failed"
-                            };
-                            let _ = tx.send(msg.to_string());
-                        }
-                        Err(e) => {
-                            let _ = tx.send(format!("ERROR:{}", e));
-                        }
+        let has_ffmpeg = self.ffmpeg_location.is_some();
+        let format_spec = if has_ffmpeg && self.merge_audio && format.is_video_only() {
+            format!("{}+bestaudio", format.format_id)
+        } else {
+            format.format_id.clone()
+        };
+
+        self.queue.push(self.url.clone(), format_spec, has_ffmpeg && self.extract_audio, scheduled);
+        self.save_queue();
+        let status = if scheduled {
+            tf("status.scheduled", &[("time", &self.config.schedule.next_open(Local::now()).format("%H:%M"))])
+        } else {
+            t("status.queued").to_string()
+        };
+        self.set_status(StatusKind::Success, status);
+        self.process_queue();
+    }
+
+    /// Handles links from outside the app: a single link is fetched so a format
+    /// can be picked, several go straight to the queue with the best format.
+    /// `lova://` links are unwrapped and select their preset when it exists.
+    pub fn open_links(&mut self, links: Vec<String>) {
+        let links: Vec<String> = links
+            .into_iter()
+            .map(|link| match scheme::parse(&link) {
+                Some(link) => {
+                    if let Some(preset) = link.preset.filter(|name| self.config.presets.iter().any(|p| &p.name == name)) {
+                        self.preset = preset;
                     }
-                });
+                    link.url
+                }
+                None => link,
+            })
+            .collect();
+        match links.len() {
+            0 => self.set_status(StatusKind::Error, t("status.no_links").to_string()),
+            1 => {
+                self.url = links.into_iter().next().unwrap_or_default();
+                self.fetch_formats();
+            }
+            _ => {
+                let mut added = 0;
+                for url in links {
+                    if let Ok(url) = links::normalize(&url) {
+                        self.enqueue_url(url, false);
+                        added += 1;
+                    }
+                }
+                self.save_queue();
+                self.set_status(StatusKind::Success, tn("status.links_queued", added, &[]));
+                self.process_queue();
             }
         }
     }
 
+    /// Queues `url` with the selected preset; returns the job id.
+    fn enqueue_url(&mut self, url: String, scheduled: bool) -> u64 {
+        let preset = self.selected_preset();
+        self.enqueue_with_preset(url, &preset, scheduled)
+    }
+
+    fn enqueue_with_preset(&mut self, url: String, preset: &Preset, scheduled: bool) -> u64 {
+        let has_ffmpeg = self.ffmpeg_location.is_some();
+        self.queue.push(url, preset.format_spec(has_ffmpeg), has_ffmpeg && preset.extract_audio, scheduled)
+    }
+
+    /// The selected preset, falling back to the first configured one.
+    pub fn selected_preset(&self) -> Preset {
+        self.config
+            .presets
+            .iter()
+            .find(|preset| preset.name == self.preset)
+            .or_else(|| self.config.presets.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Queues the URL in the input with the selected preset, skipping the format list.
+    pub fn download_with_preset(&mut self) {
+        match links::normalize(&self.url) {
+            Ok(url) => {
+                self.url = url.clone();
+                self.enqueue_url(url, false);
+                self.save_queue();
+                self.set_status(StatusKind::Success, tf("status.queued_preset", &[("preset", &self.selected_preset().name)]));
+                self.process_queue();
+            }
+            Err(e) => self.set_status(StatusKind::Error, format!("❌ {}", e)),
+        }
+    }
+
+    pub fn ui_state(&self) -> UiState {
+        UiState {
+            show_settings: self.show_settings,
+            preset: self.preset.clone(),
+        }
+    }
+
+    pub fn restore_ui_state(&mut self, state: UiState) {
+        self.show_settings = state.show_settings;
+        self.preset = state.preset;
+    }
+
+    /// Starts the next ready job when nothing is downloading.
+    pub fn process_queue(&mut self) {
+        if self.is_downloading || self.show_recovery {
+            return;
+        }
+
+        let now = Local::now();
+        let Some(id) = self.queue.next_ready(self.config.schedule.is_open(now)) else {
+            return;
+        };
+        let Some(job) = self.queue.get(id).cloned() else {
+            return;
+        };
+
+        let (mut args, credentials) = match self.base_args(&job.url) {
+            Ok(base) => base,
+            Err(e) => {
+                self.finish_job(id, Some(&tf("status.credentials_failed", &[("error", &e)])));
+                return;
+            }
+        };
+        if let Some(rate) = self.config.schedule.limit_rate(now) {
+            args.push("--limit-rate".to_string());
+            args.push(rate.to_string());
+        }
+        args.extend([
+            "-f".to_string(),
+            job.format_spec.clone(),
+            "-o".to_string(),
+            format!("{}/%(title)s.%(ext)s", self.download_dir),
+        ]);
+        if job.extract_audio {
+            args.push("-x".to_string());
+        }
+        if job.resume {
+            args.push("--continue".to_string());
+        }
+        args.push(links::classify(&job.url).yt_dlp_flag().to_string());
+        args.extend([
+            "--progress".to_string(),
+            "--newline".to_string(),
+            "--progress-template".to_string(),
+            Progress::TEMPLATE.to_string(),
+        ]);
+        // Report where each file ended up as one JSON line per file.
+        args.extend([
+            "--print".to_string(),
+            format!("after_move:{}%(.{{filepath,title}})j", PRINTED_FILE_PREFIX),
+        ]);
+        // Reuse the extraction from the format list while its URLs are fresh.
+        let cached = self.info_cache_ttl().and_then(|ttl| info_cache::fresh_path(&self.info_cache_key(&job.url), ttl));
+        let from_cache = cached.is_some();
+        match cached {
+            Some(path) => {
+                args.push("--load-info-json".to_string());
+                args.push(path.to_string_lossy().to_string());
+            }
+            None => args.push(job.url.clone()),
+        }
+
+        if let Some(job) = self.queue.get_mut(id) {
+            job.state = JobState::Running;
+        }
+        self.save_queue();
+        self.is_downloading = true;
+        self.set_status(StatusKind::Info, t("status.downloading").to_string());
+        let yt_dlp_path = self.yt_dlp_binary();
+        self.last_command = auth::redact_command(&yt_dlp_path, &args);
+        self.twofactor_code.clear();
+        let tx = self.tx.clone();
+        self.cancel_download = Arc::new(AtomicBool::new(false));
+        let cancel = self.cancel_download.clone();
+
+        self.runtime.spawn(async move {
+            let mut printed = None;
+            let mut errors = None;
+            let result = match Command::new(&yt_dlp_path)
+                .args(&args)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+            {
+                Ok(mut child) => loop {
+                    if let Some(stdout) = child.stdout.take() {
+                        let tx = tx.clone();
+                        printed = Some(std::thread::spawn(move || read_job_output(stdout, id, &tx)));
+                    }
+                    if let Some(stderr) = child.stderr.take() {
+                        let tx = tx.clone();
+                        errors = Some(std::thread::spawn(move || read_job_output(stderr, id, &tx)));
+                    }
+                    if cancel.load(Ordering::Relaxed) {
+                        let _ = child.kill();
+                    }
+                    match child.try_wait() {
+                        Ok(Some(status)) => break Ok(status),
+                        Ok(None) => tokio::time::sleep(std::time::Duration::from_millis(200)).await,
+                        Err(e) => break Err(e),
+                    }
+                },
+                Err(e) => Err(e),
+            };
+            drop(credentials);
+
+            let msg = match result {
+                Ok(status) if status.success() => {
+                    let output = printed.and_then(|reader| reader.join().ok()).unwrap_or_default();
+                    let files = parse_printed_files(&output);
+                    format!("JOB_DONE:{}\n{}", id, serde_json::to_string(&files).unwrap_or_default())
+                }
+                Ok(_) if cancel.load(Ordering::Relaxed) => format!("JOB_FAILED:{}\n{}", id, t("status.cancelled")),
+                // Expired format URLs in the cached JSON; extract again.
+                Ok(_) if from_cache => format!("JOB_STALE:{}", id),
+                Ok(_) => {
+                    // yt-dlp explains failures in its last "ERROR:" line.
+                    let output = errors.and_then(|reader| reader.join().ok()).unwrap_or_default();
+                    let error = output
+                        .lines()
+                        .rev()
+                        .find_map(|line| line.strip_prefix("ERROR:"))
+                        .map(str::trim)
+                        .unwrap_or(t("status.download_failed"))
+                        .to_string();
+                    format!("JOB_FAILED:{}\n{}", id, error)
+                }
+                Err(e) => format!("JOB_FAILED:{}\n{}", id, e),
+            };
+            let _ = tx.send(msg);
+        });
+    }
+
+    /// Stops the running download; the job ends up failed and can be retried.
+    pub fn cancel_running_download(&mut self) {
+        if self.is_downloading {
+            self.cancel_download.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Remembers the egui context so background tasks can wake the UI, and
+    /// starts the HTTP API and clipboard watcher if they are enabled.
+    pub fn set_egui_context(&mut self, ctx: egui::Context) {
+        self.egui_ctx = Some(ctx);
+        self.restart_api();
+        self.set_clipboard_watch(self.config.clipboard.enabled);
+    }
+
+    /// Applies the API settings: stops a running server and starts a new one
+    /// when enabled, creating a token on first use.
+    pub fn restart_api(&mut self) {
+        self.api_server = None;
+        let Some(ctx) = self.egui_ctx.clone() else {
+            return;
+        };
+        if !self.config.api.enabled {
+            return;
+        }
+        if self.secrets.api_token.is_empty() {
+            self.regenerate_api_token();
+            if self.secrets.api_token.is_empty() {
+                return;
+            }
+        }
+        match api::spawn_server(&self.config.api, self.secrets.api_token.clone(), self.api_tx.clone(), ctx) {
+            Ok(server) => self.api_server = Some(server),
+            Err(e) => {
+                self.set_status(StatusKind::Error, tf("status.api_failed", &[("port", &self.config.api.port), ("error", &e)]));
+            }
+        }
+    }
+
+    /// Receives links from later launches; see [`crate::instance`].
+    pub fn listen_for_instances(&self, listener: InstanceListener) {
+        if let Some(ctx) = self.egui_ctx.clone() {
+            listener.spawn(self.tx.clone(), ctx);
+        }
+    }
+
+    /// The URL plus everything that changes what yt-dlp extracts for it.
+    fn info_cache_key(&self, url: &str) -> String {
+        format!("{}\n{:?}\n{:?}", url, self.config.network.args(), self.auth_profile_for(url))
+    }
+
+    fn info_cache_ttl(&self) -> Option<Duration> {
+        (self.config.info_cache_minutes > 0).then(|| Duration::from_secs(u64::from(self.config.info_cache_minutes) * 60))
+    }
+
+    pub fn clear_info_cache(&mut self) {
+        self.formats_cached = false;
+        self.set_status(StatusKind::Info, tn("status.info_cache_cleared", info_cache::clear(), &[]));
+    }
+
+    pub fn register_scheme(&mut self) {
+        match scheme::register() {
+            Ok(()) => self.set_status(StatusKind::Success, tf("status.scheme_registered", &[("scheme", &scheme::SCHEME)])),
+            Err(e) => self.set_status(StatusKind::Error, tf("status.scheme_failed", &[("error", &e)])),
+        }
+    }
+
+    pub fn is_api_running(&self) -> bool {
+        self.api_server.is_some()
+    }
+
+    pub fn regenerate_api_token(&mut self) {
+        match api::generate_token() {
+            Ok(token) => {
+                self.secrets.api_token = token;
+                self.save_secrets();
+            }
+            Err(e) => self.set_status(StatusKind::Error, tf("status.token_failed", &[("error", &e)])),
+        }
+    }
+
+    fn answer_api(&mut self, request: ApiRequest) -> ApiReply {
+        match request {
+            ApiRequest::ListJobs => Ok(self.queue.jobs.iter().map(job_json).collect()),
+            ApiRequest::GetJob(id) => self.queue.get(id).map(job_json).ok_or((404, t("api.no_job").to_string())),
+            ApiRequest::Enqueue { url, preset } => {
+                let url = links::normalize(&url).map_err(|e| (400, e))?;
+                let preset = match preset {
+                    Some(name) => self
+                        .config
+                        .presets
+                        .iter()
+                        .find(|preset| preset.name == name)
+                        .cloned()
+                        .ok_or((400, tf("api.no_preset", &[("name", &name)])))?,
+                    None => self.selected_preset(),
+                };
+                let id = self.enqueue_with_preset(url, &preset, false);
+                self.save_queue();
+                self.set_status(StatusKind::Success, tf("status.queued_preset", &[("preset", &preset.name)]));
+                self.process_queue();
+                Ok(self.queue.get(id).map(job_json).unwrap_or_default())
+            }
+            ApiRequest::Cancel(id) => {
+                let conflict = self.queue.get(id).is_some();
+                self.cancel_job(id)
+                    .map(|()| serde_json::json!({ "id": id }))
+                    .map_err(|e| (if conflict { 409 } else { 404 }, e))
+            }
+            ApiRequest::ListPresets => Ok(serde_json::to_value(&self.config.presets).unwrap_or_default()),
+        }
+    }
+
+    /// Stops a running job or takes a waiting one off the queue.
+    pub fn cancel_job(&mut self, id: u64) -> Result<(), String> {
+        match self.queue.get(id).map(|job| job.state) {
+            Some(JobState::Running) => self.cancel_running_download(),
+            Some(JobState::Queued) => self.remove_job(id),
+            Some(_) => return Err(t("api.not_cancellable").to_string()),
+            None => return Err(t("api.no_job").to_string()),
+        }
+        Ok(())
+    }
+
+    pub fn remove_job(&mut self, id: u64) {
+        self.queue.remove(id);
+        self.save_queue();
+    }
+
+    pub fn retry_job(&mut self, id: u64) {
+        self.queue.retry(id);
+        self.save_queue();
+        self.process_queue();
+    }
+
+    /// Starts or stops polling the clipboard for supported links.
+    pub fn set_clipboard_watch(&mut self, enabled: bool) {
+        self.config.clipboard.enabled = enabled;
+        // Replace the flag so a stopped watcher can never be revived by a restart.
+        self.clipboard_watching.store(false, Ordering::Relaxed);
+        self.clipboard_watching = Arc::new(AtomicBool::new(enabled));
+        // Started by `set_egui_context` once the UI exists.
+        if let (true, Some(ctx)) = (enabled, self.egui_ctx.clone()) {
+            clipboard::spawn_watcher(&self.runtime, self.tx.clone(), self.clipboard_watching.clone(), ctx);
+        }
+    }
+
+    fn handle_clipboard_text(&mut self, text: &str) {
+        let urls = match self.clipboard_patterns.get(&self.config.clipboard.patterns) {
+            Ok(patterns) => clipboard::find_urls(text, patterns),
+            Err(e) => {
+                self.clipboard_error = e.clone();
+                return;
+            }
+        };
+        self.clipboard_error.clear();
+
+        for url in urls {
+            let url = links::normalize(&url).unwrap_or(url);
+            if self.is_known_url(&url) {
+                continue;
+            }
+            if self.config.clipboard.auto_fetch && !self.is_fetching && !self.is_downloading {
+                self.url = url;
+                self.fetch_formats();
+            } else {
+                self.detected_links.push(url);
+            }
+        }
+    }
+
+    /// Whether normalized `url` is already in the input, the tray or the job
+    /// list. Completed jobs are not restored on startup, so a link downloaded
+    /// in an earlier session counts as new again.
+    fn is_known_url(&self, url: &str) -> bool {
+        let same = |other: &str| links::normalize(other).is_ok_and(|other| other == url) || other == url;
+        same(&self.url)
+            || self.detected_links.iter().any(|link| same(link))
+            || self.queue.jobs.iter().any(|job| same(&job.url))
+    }
+
+    /// Moves a detected link into the URL field and fetches its formats.
+    pub fn fetch_detected_link(&mut self, index: usize) {
+        if index < self.detected_links.len() {
+            self.url = self.detected_links.remove(index);
+            self.fetch_formats();
+        }
+    }
+
+    /// Answers the startup prompt about jobs left over from the last session.
+    pub fn recover_jobs(&mut self, resume: bool) {
+        if resume {
+            self.queue.resume_all();
+        } else {
+            self.queue.discard_unfinished();
+        }
+        self.show_recovery = false;
+        self.save_queue();
+        self.process_queue();
+    }
+
+    pub fn clear_finished_jobs(&mut self) {
+        self.queue.clear_finished();
+        self.save_queue();
+    }
+
+    fn save_queue(&mut self) {
+        if let Err(e) = self.queue.save() {
+            self.set_status(StatusKind::Error, tf("status.queue_save_failed", &[("error", &e)]));
+        }
+    }
+
+    fn finish_job(&mut self, id: u64, error: Option<&str>) {
+        if let Some(job) = self.queue.get_mut(id) {
+            match error {
+                Some(error) => {
+                    job.state = JobState::Failed;
+                    job.error = error.to_string();
+                }
+                None => job.state = JobState::Completed,
+            }
+            job.progress = None;
+            let file = job.files.last().cloned();
+            let (event, body) = match (error, &file) {
+                (Some(error), _) => (NotificationEvent::Failed, format!("{}\n{}", job.url, error)),
+                (None, Some(file)) => (NotificationEvent::Completed, file.title.clone()),
+                (None, None) => (NotificationEvent::Completed, job.url.clone()),
+            };
+            if self.config.notifications.wants(event) {
+                let path = file.as_ref().map(|file| PathBuf::from(&file.path));
+                notifications::show(event, body, path, PathBuf::from(&self.download_dir));
+            }
+
+            if error.is_none() {
+                let url = job.url.clone();
+                for file in job.files.clone() {
+                    self.run_post_download_command(&url, &file);
+                }
+                self.last_download = file;
+            }
+        }
+        self.is_downloading = false;
+        match error {
+            Some(error) => self.set_status(StatusKind::Error, format!("❌ {}", error)),
+            None => self.set_status(StatusKind::Success, t("status.download_completed").to_string()),
+        }
+        self.save_queue();
+        self.process_queue();
+    }
+
+    /// Runs the configured post-download command for one file in the background.
+    fn run_post_download_command(&self, url: &str, file: &DownloadedFile) {
+        let template = self.config.post_download_command.trim();
+        if template.is_empty() {
+            return;
+        }
+        let command = desktop::expand_command(template, &[
+            ("path", &file.path),
+            ("title", &file.title),
+            ("url", url),
+        ]);
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            match desktop::run_shell(&command) {
+                Ok(status) if status.success() => {}
+                Ok(status) => {
+                    let _ = tx.send(format!("HOOK_FAILED:{}", status));
+                }
+                Err(e) => {
+                    let _ = tx.send(format!("HOOK_FAILED:{}", e));
+                }
+            }
+        });
+    }
+
+    pub fn open_file(&mut self, path: &str) {
+        if let Err(e) = desktop::open_path(Path::new(path)) {
+            self.set_status(StatusKind::Error, tf("status.open_failed", &[("error", &e)]));
+        }
+    }
+
+    pub fn reveal_file(&mut self, path: &str) {
+        if let Err(e) = desktop::reveal_path(Path::new(path)) {
+            self.set_status(StatusKind::Error, tf("status.open_failed", &[("error", &e)]));
+        }
+    }
+
+    /// Arguments shared by every yt-dlp run for `url`, and the credentials file
+    /// they point at, which must outlive the process.
+    fn base_args(&self, url: &str) -> std::io::Result<(Vec<String>, Option<auth::CredentialsFile>)> {
+        let mut args = self.config.network.args();
+        if let Some(location) = &self.ffmpeg_location {
+            args.push("--ffmpeg-location".to_string());
+            args.push(location.clone());
+        }
+        let mut credentials = None;
+        if let Some(profile) = self.auth_profile_for(url) {
+            let (profile_args, file) = profile.args(&self.secrets, &self.twofactor_code)?;
+            args.extend(profile_args);
+            credentials = file;
+        }
+        Ok((args, credentials))
+    }
+
+    /// The authentication profile for the URL in the input field.
+    pub fn auth_profile(&self) -> Option<&auth::AuthProfile> {
+        self.auth_profile_for(&self.url)
+    }
+
+    fn auth_profile_for(&self, url: &str) -> Option<&auth::AuthProfile> {
+        self.config.auth_profiles.iter().find(|profile| profile.matches(url))
+    }
+
+    /// Re-reads everything derived from `config` after it was replaced wholesale.
+    fn config_replaced(&mut self) {
+        i18n::set_language(self.config.language);
+        self.restart_api();
+        self.yt_dlp_path = self.config.yt_dlp_path.clone();
+        self.download_dir = self.config.download_dir.clone();
+        self.profile_name = self.config.active_profile.clone();
+        self.set_clipboard_watch(self.config.clipboard.enabled);
+        self.check_yt_dlp();
+        self.check_ffmpeg();
+        self.save_config();
+    }
+
+    pub fn apply_profile(&mut self, name: &str) {
+        self.save_config();
+        let mut errors = Vec::new();
+        if self.config.apply_profile(name, &mut errors) {
+            self.config_errors.extend(errors);
+            self.config_replaced();
+            self.set_status(StatusKind::Success, tf("status.profile_switched", &[("name", &name)]));
+        }
+    }
+
+    pub fn save_profile(&mut self) {
+        let name = self.profile_name.trim().to_string();
+        if name.is_empty() {
+            self.set_status(StatusKind::Error, t("status.profile_name_missing").to_string());
+            return;
+        }
+        self.save_config();
+        self.config.save_profile(&name);
+        self.save_config();
+        self.set_status(StatusKind::Success, tf("status.profile_saved", &[("name", &name)]));
+    }
+
+    pub fn delete_profile(&mut self, name: &str) {
+        self.config.delete_profile(name);
+        self.save_config();
+    }
+
+    pub fn export_config(&mut self, path: &std::path::Path) {
+        self.save_config();
+        match self.config.export(path) {
+            Ok(()) => self.set_status(StatusKind::Success, tf("status.exported", &[("path", &path.display())])),
+            Err(e) => self.set_status(StatusKind::Error, tf("status.export_failed", &[("error", &e)])),
+        }
+    }
+
+    pub fn import_config(&mut self, path: &std::path::Path) {
+        self.save_config();
+        match Config::import(path, &self.config) {
+            Ok((config, errors)) => {
+                self.config = config;
+                self.config_errors = errors;
+                self.config_replaced();
+                self.set_status(StatusKind::Success, tf("status.imported", &[("path", &path.display())]));
+            }
+            Err(e) => self.set_status(StatusKind::Error, tf("status.import_failed", &[("error", &e)])),
+        }
+    }
+
+    pub fn save_secrets(&mut self) {
+        if let Err(e) = self.secrets.save() {
+            self.set_status(StatusKind::Error, tf("status.secrets_save_failed", &[("error", &e)]));
+        }
+    }
+
+    /// The yt-dlp binary to run: the one found by the last check, else the configured path.
+    fn yt_dlp_binary(&self) -> String {
+        self.yt_dlp_resolved.clone().unwrap_or_else(|| self.yt_dlp_path.clone())
+    }
+
+    /// Verifies the configured yt-dlp binary in the background, falling back to `PATH`.
+    pub fn check_yt_dlp(&mut self) {
+        self.yt_dlp_version = None;
+        self.pending_checks += 1;
+        let yt_dlp_path = self.yt_dlp_path.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let _ = tx.send(yt_dlp_status_message(&yt_dlp_path));
+        });
+    }
+
+    /// Locates ffmpeg in the background; an empty `ffmpeg_path` means auto-detect.
+    pub fn check_ffmpeg(&mut self) {
+        self.ffmpeg_version = None;
+        self.ffmpeg_location = None;
+        self.ffmpeg_error.clear();
+        self.pending_checks += 1;
+        let ffmpeg_path = self.config.ffmpeg_path.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let msg = match ffmpeg::resolve(&ffmpeg_path) {
+                Ok((path, version)) => format!("FFMPEG_FOUND:{}\n{}", version, path),
+                Err(e) => format!("FFMPEG_MISSING:{}", e),
+            };
+            let _ = tx.send(msg);
+        });
+    }
+
+    /// Runs `yt-dlp -U`, or switches to `channel` when one is given.
+    pub fn update_yt_dlp(&mut self, channel: Option<UpdateChannel>) {
+        if self.is_updating {
+            return;
+        }
+
+        self.is_updating = true;
+        self.pending_checks += 1;
+        self.yt_dlp_output.clear();
+        self.set_status(StatusKind::Info, t("status.updating").to_string());
+        let binary = self.yt_dlp_binary();
+        let yt_dlp_path = self.yt_dlp_path.clone();
+        let tx = self.tx.clone();
+
+        self.runtime.spawn(async move {
+            let msg = match ytdlp::self_update(&binary, channel) {
+                Ok(output) => format!("YTDLP_UPDATED:{}", output),
+                Err(output) => format!("YTDLP_UPDATE_FAILED:{}", output),
+            };
+            let _ = tx.send(msg);
+            let _ = tx.send(yt_dlp_status_message(&yt_dlp_path));
+        });
+    }
+
     fn process_status_message(&mut self, message: &str) {
         if let Some((status_type, content)) = message.split_once(':') {
             match status_type {
-                "FORMATS" => {
+                "FORMATS_ENTRY" => {
                     if let Ok(formats) = serde_json::from_str::<Vec<FormatInfo>>(content) {
-                        self.formats = formats;
-                        self.status = format!("✅ Found {} formats", self.formats.len());
+                        for format in formats {
+                            if !self.formats.iter().any(|known| known.format_id == format.format_id) {
+                                self.formats.push(format);
+                            }
+                        }
+                        self.set_status(StatusKind::Info, tn("status.formats_so_far", self.formats.len(), &[]));
+                    }
+                }
+                "FORMATS_DONE" => {
+                    if self.formats.is_empty() {
+                        self.set_status(StatusKind::Error, format!("❌ {}", t("status.no_formats")));
+                    } else {
+                        self.set_status(StatusKind::Success, tn("status.formats_found", self.formats.len(), &[]));
                     }
                     self.is_fetching = false;
                 }
-                "SUCCESS" => {
-                    self.status = format!("✅ {}", content);
-                    self.is_downloading = false;
+                "JOB_DONE" => {
+                    let (id, files) = content.split_once('\n').unwrap_or((content, ""));
+                    if let Ok(id) = id.parse() {
+                        if let Some(job) = self.queue.get_mut(id) {
+                            job.files = serde_json::from_str(files).unwrap_or_default();
+                        }
+                        self.finish_job(id, None);
+                    }
+                }
+                "JOB_PROGRESS" => {
+                    if let Some((id, progress)) = content.split_once('\n') {
+                        if let (Ok(id), Ok(progress)) = (id.parse(), serde_json::from_str(progress)) {
+                            if let Some(job) = self.queue.get_mut(id) {
+                                job.progress = Some(progress);
+                            }
+                        }
+                    }
+                }
+                "INSTANCE" => {
+                    let links: Vec<String> = serde_json::from_str(content).unwrap_or_default();
+                    if links.is_empty() {
+                        self.set_status(StatusKind::Info, t("status.already_running").to_string());
+                    } else {
+                        self.open_links(links);
+                    }
+                }
+                "JOB_STALE" => {
+                    if let Ok(id) = content.parse() {
+                        if let Some(url) = self.queue.get(id).map(|job| job.url.clone()) {
+                            info_cache::remove(&self.info_cache_key(&url));
+                        }
+                        if let Some(job) = self.queue.get_mut(id) {
+                            job.state = JobState::Queued;
+                            job.resume = true;
+                            job.progress = None;
+                        }
+                        self.is_downloading = false;
+                        self.save_queue();
+                    }
+                }
+                "HOOK_FAILED" => {
+                    self.set_status(StatusKind::Error, tf("status.hook_failed", &[("error", &content)]));
+                }
+                "JOB_FAILED" => {
+                    if let Some((id, error)) = content.split_once('\n') {
+                        if let Ok(id) = id.parse() {
+                            self.finish_job(id, Some(error));
+                        }
+                    }
+                }
+                "YTDLP_FOUND" => {
+                    self.pending_checks = self.pending_checks.saturating_sub(1);
+                    if let Some((version, path)) = content.split_once('\n') {
+                        self.yt_dlp_version = Some(version.to_string());
+                        self.yt_dlp_resolved = Some(path.to_string());
+                    }
+                }
+                "YTDLP_MISSING" => {
+                    self.pending_checks = self.pending_checks.saturating_sub(1);
+                    self.yt_dlp_version = None;
+                    self.yt_dlp_resolved = None;
+                    self.set_status(StatusKind::Error, format!("❌ {}", content));
+                }
+                "FFMPEG_FOUND" => {
+                    self.pending_checks = self.pending_checks.saturating_sub(1);
+                    if let Some((version, path)) = content.split_once('\n') {
+                        self.ffmpeg_version = Some(version.to_string());
+                        self.ffmpeg_location = Some(path.to_string());
+                    }
+                }
+                "FFMPEG_MISSING" => {
+                    self.pending_checks = self.pending_checks.saturating_sub(1);
+                    self.ffmpeg_error = content.to_string();
+                }
+                "CLIPBOARD" => {
+                    self.handle_clipboard_text(content);
+                }
+                // Kept apart from `ERROR`, which would end a running fetch.
+                "CLIPBOARD_ERROR" => {
+                    self.set_status(StatusKind::Error, format!("❌ {}", content));
+                    self.clipboard_error = content.to_string();
+                }
+                "YTDLP_UPDATED" => {
+                    self.yt_dlp_output = content.to_string();
+                    self.set_status(StatusKind::Success, t("status.update_completed").to_string());
+                    self.is_updating = false;
+                }
+                "YTDLP_UPDATE_FAILED" => {
+                    self.yt_dlp_output = content.to_string();
+                    self.set_status(StatusKind::Error, t("status.update_failed").to_string());
+                    self.is_updating = false;
                 }
                 "ERROR" => {
-                    self.status = format!("❌ {}", content);
+                    self.set_status(StatusKind::Error, format!("❌ {}", content));
                     self.is_fetching = false;
-                    self.is_downloading = false;
                 }
                 _ => {}
             }
         }
     }
 
+    pub fn set_status(&mut self, kind: StatusKind, status: String) {
+        self.status = status;
+        self.status_kind = kind;
+    }
+
     pub fn save_config(&mut self) {
+        self.config.yt_dlp_path = self.yt_dlp_path.clone();
+        self.config.download_dir = self.download_dir.clone();
         if let Err(e) = self.config.save() {
-            self.status = format!("❌ Failed to save config: {}", e);
+            self.set_status(StatusKind::Error, tf("status.config_save_failed", &[("error", &e)]));
         }
     }
 
@@ -192,54 +1190,196 @@ impl YtDlpApp {
         while let Ok(message) = self.rx.try_recv() {
             self.process_status_message(&message);
         }
+        while let Ok(pending) = self.api_rx.try_recv() {
+            let reply = self.answer_api(pending.request);
+            let _ = pending.reply.send(reply);
+        }
+        self.process_queue();
     }
 
     pub fn is_busy(&self) -> bool {
-        self.is_fetching || self.is_downloading
+        self.is_fetching || self.is_downloading || self.is_updating || self.pending_checks > 0
+    }
+
+    /// Jobs are waiting for the schedule window, so the UI must keep ticking.
+    pub fn has_pending_jobs(&self) -> bool {
+        self.queue.has_pending()
+    }
+
+    pub fn is_updating(&self) -> bool {
+        self.is_updating
+    }
+
+    pub fn is_downloading(&self) -> bool {
+        self.is_downloading
+    }
+
+    pub fn run_action(&mut self, action: Action) {
+        match action {
+            Action::PasteAndFetch => {
+                if let Ok(text) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+                    self.url = text;
+                    self.fetch_formats();
+                }
+            }
+            Action::FetchFormats => {
+                if !self.url.is_empty() {
+                    self.fetch_formats();
+                }
+            }
+            Action::Download => self.download_selected_format(),
+            Action::QuickDownload => {
+                if !self.url.is_empty() {
+                    self.download_with_preset();
+                }
+            }
+            Action::Schedule => self.schedule_selected_format(),
+            Action::Cancel => self.confirm_cancel = self.is_downloading,
+            Action::NextFormat => self.move_format_selection(1),
+            Action::PreviousFormat => self.move_format_selection(-1),
+            Action::ToggleSettings => self.show_settings = !self.show_settings,
+            Action::CommandPalette => {
+                self.show_palette = !self.show_palette;
+                self.palette_query.clear();
+                self.palette_index = 0;
+            }
+            Action::Clear => self.clear_state(),
+            Action::UpdateYtDlp => {
+                if self.yt_dlp_version.is_some() && !self.is_updating {
+                    self.update_yt_dlp(None);
+                }
+            }
+            Action::ClearFinished => self.clear_finished_jobs(),
+        }
+    }
+
+    fn move_format_selection(&mut self, step: isize) {
+        if self.formats.is_empty() {
+            return;
+        }
+        let last = self.formats.len() - 1;
+        self.selected_format = Some(match self.selected_format {
+            Some(index) => index.saturating_add_signed(step).min(last),
+            None if step < 0 => last,
+            None => 0,
+        });
     }
 
     pub fn clear_state(&mut self) {
         self.url.clear();
+        self.last_download = None;
         self.formats.clear();
+        self.formats_cached = false;
         self.selected_format = None;
         self.status.clear();
+        self.last_command.clear();
     }
 }
 
-fn process_video_response(json_str: &str) -> Result<Vec<FormatInfo>, Box<dyn std::error::Error>> {
-    let response: VideoResponse = serde_json::from_str(json_str)?;
-    
-    let formats = match response {
-        VideoResponse::Single(video) => {
-            if !video.formats.is_empty() {
-                video.formats
-            } else if let (Some(format_id), Some(ext)) = (video.format_id, video.ext) {
-                // Single format case (like direct media files)
-                vec![FormatInfo {
-                    format_id,
-                    ext,
-                    resolution: video.resolution,
-                    vcodec: video.vcodec.unwrap_or_else(|| "unknown".to_string()),
-                    acodec: video.acodec,
-                }]
-            } else {
-                vec![]
+fn job_json(job: &Job) -> serde_json::Value {
+    serde_json::json!({
+        "id": job.id,
+        "url": job.url,
+        "format": job.format_spec,
+        "state": job.state,
+        "scheduled": job.scheduled,
+        "progress": job.progress,
+        "error": job.error,
+        "files": job.files,
+    })
+}
+
+/// Forwards progress lines of a running job as `JOB_PROGRESS` messages and
+/// returns everything else once the stream closes.
+fn read_job_output(stream: impl Read, id: u64, tx: &Sender<String>) -> String {
+    let mut output = String::new();
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        match Progress::parse(&line) {
+            Some(progress) => {
+                let _ = tx.send(format!("JOB_PROGRESS:{}\n{}", id, serde_json::to_string(&progress).unwrap_or_default()));
+            }
+            None => {
+                output.push_str(&line);
+                output.push('\n');
             }
         }
-        VideoResponse::Multiple(videos) => {
-            videos.into_iter()
-                .flat_map(|v| v.formats)
-                .collect()
-        }
-    };
+    }
+    output
+}
+
+/// Marks the lines printed by `--print after_move:…` among other output.
+const PRINTED_FILE_PREFIX: &str = "LOVA_FILE ";
+
+#[derive(Deserialize)]
+struct PrintedFile {
+    filepath: String,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+/// Reads the `{"filepath": …, "title": …}` records printed for each file.
+fn parse_printed_files(output: &str) -> Vec<DownloadedFile> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(PRINTED_FILE_PREFIX))
+        .filter_map(|json| serde_json::from_str::<PrintedFile>(json).ok())
+        .filter(|file| !file.filepath.is_empty())
+        .map(|file| DownloadedFile {
+            path: file.filepath,
+            title: file.title.unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn yt_dlp_status_message(configured: &str) -> String {
+    match ytdlp::resolve(configured) {
+        Ok((path, version)) => format!("YTDLP_FOUND:{}\n{}", version, path),
+        Err(e) => format!("YTDLP_MISSING:{}", e),
+    }
+}
+
+/// Parses yt-dlp's JSON-lines output, one video per line, keeping the first
+/// format of each ID.
+fn process_video_response(json_str: &str) -> Result<Vec<FormatInfo>, Box<dyn std::error::Error>> {
+    let mut formats = Vec::new();
+    let mut seen = HashSet::new();
+    for line in json_str.lines().filter(|line| !line.trim().is_empty()) {
+        formats.extend(entry_formats(serde_json::from_str(line)?).into_iter().filter(|format| seen.insert(format.format_id.clone())));
+    }
 
     if formats.is_empty() {
-        return Err("No formats found".into());
+        return Err(t("status.no_formats").into());
     }
 
     Ok(formats)
 }
 
+fn entry_formats(video: SingleVideo) -> Vec<FormatInfo> {
+    if !video.formats.is_empty() {
+        video.formats
+    } else if let (Some(format_id), Some(ext)) = (video.format_id, video.ext) {
+        // Single format case (like direct media files)
+        vec![FormatInfo {
+            format_id,
+            ext,
+            resolution: video.resolution,
+            vcodec: video.vcodec.unwrap_or_else(|| "unknown".to_string()),
+            acodec: video.acodec,
+        }]
+    } else {
+        vec![]
+    }
+}
+
+/// The message of the last `ERROR:` line yt-dlp wrote to `stream`.
+fn last_error_line(stream: impl Read) -> Option<String> {
+    BufReader::new(stream)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| line.strip_prefix("ERROR:").map(|error| error.trim().to_string()))
+        .last()
+}
+
 pub fn short_codec(codec: &str) -> String {
     match codec {
         "avc1" | "h264" => "H.264".to_string(),
@@ -251,3 +1391,39 @@ pub fn short_codec(codec: &str) -> String {
         _ => codec.split('.').next().unwrap_or(codec).to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_one_record_per_file() {
+        let output = "[download] Destination: a.mkv\n\
+                      LOVA_FILE {\"filepath\": \"/music/a b.opus\", \"title\": \"A \\\"b\\\"\"}\n\
+                      LOVA_FILE {\"filepath\": \"/music/c.opus\", \"title\": \"C\"}\n";
+        assert_eq!(
+            parse_printed_files(output),
+            [
+                DownloadedFile { path: "/music/a b.opus".to_string(), title: "A \"b\"".to_string() },
+                DownloadedFile { path: "/music/c.opus".to_string(), title: "C".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_files_with_empty_or_missing_titles() {
+        let output = "LOVA_FILE {\"filepath\": \"/a.mp4\", \"title\": \"\"}\n\
+                      LOVA_FILE {\"filepath\": \"/b.mp4\", \"title\": null}\n\
+                      LOVA_FILE {\"filepath\": \"/c.mp4\"}\n";
+        let titles: Vec<String> = parse_printed_files(output).into_iter().map(|file| file.title).collect();
+        assert_eq!(titles, ["", "", ""]);
+    }
+
+    #[test]
+    fn skips_malformed_records() {
+        let output = "LOVA_FILE {\"filepath\": \"\", \"title\": \"x\"}\n\
+                      LOVA_FILE not json\n\
+                      {\"filepath\": \"/unprefixed.mp4\"}\n";
+        assert!(parse_printed_files(output).is_empty());
+    }
+}
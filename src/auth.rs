@@ -0,0 +1,174 @@
+use crate::config::{self, Config};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Arguments whose following value must never be shown or logged.
+const SECRET_FLAGS: [&str; 4] = ["-p", "--password", "--twofactor", "--video-password"];
+
+/// Per-site authentication settings stored in the plain config.
+/// Passwords live in [`SecretStore`], keyed by `site`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthProfile {
+    /// Domain the profile applies to, e.g. `youtube.com` (subdomains match too).
+    pub site: String,
+    #[serde(default)]
+    pub cookies_file: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub use_netrc: bool,
+    #[serde(default)]
+    pub twofactor: bool,
+}
+
+impl AuthProfile {
+    pub fn matches(&self, url: &str) -> bool {
+        let site = self.site.trim().trim_start_matches("www.").to_lowercase();
+        if site.is_empty() {
+            return false;
+        }
+        match host_of(url) {
+            Some(host) => host == site || host.ends_with(&format!(".{}", site)),
+            None => false,
+        }
+    }
+
+    /// yt-dlp arguments for this profile. Credentials are written to a
+    /// private config file passed with `--config-location`, so they never show
+    /// up in the process list; keep the returned file until yt-dlp exits.
+    /// `twofactor_code` is only used when the profile asks for it.
+    pub fn args(&self, secrets: &SecretStore, twofactor_code: &str) -> io::Result<(Vec<String>, Option<CredentialsFile>)> {
+        let mut args = Vec::new();
+        let mut credentials = Vec::new();
+        if !self.cookies_file.trim().is_empty() {
+            args.push("--cookies".to_string());
+            args.push(self.cookies_file.trim().to_string());
+        }
+        if self.use_netrc {
+            args.push("--netrc".to_string());
+        } else if !self.username.is_empty() {
+            credentials.push(("-u", self.username.as_str()));
+            if let Some(password) = secrets.password(&self.site) {
+                credentials.push(("-p", password));
+            }
+        }
+        if self.twofactor && !twofactor_code.trim().is_empty() {
+            credentials.push(("--twofactor", twofactor_code.trim()));
+        }
+        if credentials.is_empty() {
+            return Ok((args, None));
+        }
+
+        let file = CredentialsFile::create(&credentials)?;
+        args.push("--config-location".to_string());
+        args.push(file.path.to_string_lossy().to_string());
+        Ok((args, Some(file)))
+    }
+}
+
+/// A yt-dlp config file holding login options, readable by the current user
+/// only and deleted when dropped.
+pub struct CredentialsFile {
+    path: PathBuf,
+}
+
+impl CredentialsFile {
+    fn create(options: &[(&str, &str)]) -> io::Result<Self> {
+        let dir = Config::get_cache_dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("credentials-{}.conf", crate::api::generate_token()?));
+        // yt-dlp splits config files like a POSIX shell.
+        let contents: String = options
+            .iter()
+            .map(|(flag, value)| format!("{} \"{}\"\n", flag, value.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        config::write_private(&path, contents.as_bytes())?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for CredentialsFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Passwords kept in a separate file next to the config so `lova.json`
+/// can be shared without leaking credentials.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SecretStore {
+    passwords: HashMap<String, String>,
+    /// Token for the local HTTP API.
+    #[serde(default)]
+    pub api_token: String,
+}
+
+impl SecretStore {
+    pub fn load() -> Self {
+        fs::read_to_string(Config::get_secrets_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Config::get_secrets_path();
+        let json = serde_json::to_string_pretty(self)?;
+        config::write_private(&path, json.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn password(&self, site: &str) -> Option<&str> {
+        self.passwords
+            .get(site)
+            .map(String::as_str)
+            .filter(|password| !password.is_empty())
+    }
+
+    pub fn password_mut(&mut self, site: &str) -> &mut String {
+        self.passwords.entry(site.to_string()).or_default()
+    }
+
+    /// Moves the password of `from` to `to` after a profile's site was renamed.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if from != to {
+            if let Some(password) = self.passwords.remove(from) {
+                self.passwords.insert(to.to_string(), password);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, site: &str) {
+        self.passwords.remove(site);
+    }
+}
+
+/// Returns the lowercase host of `url` without `www.`, port or credentials.
+pub fn host_of(url: &str) -> Option<String> {
+    let rest = url.trim().split_once("://").map_or(url.trim(), |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    if host.is_empty() {
+        return None;
+    }
+    Some(host.trim_start_matches("www.").to_lowercase())
+}
+
+/// Joins a command line for display, masking secret argument values.
+pub fn redact_command(program: &str, args: &[String]) -> String {
+    let mut parts = vec![program.to_string()];
+    let mut hide_next = false;
+    for arg in args {
+        if hide_next {
+            parts.push("****".to_string());
+            hide_next = false;
+            continue;
+        }
+        hide_next = SECRET_FLAGS.contains(&arg.as_str());
+        parts.push(if arg.contains(' ') { format!("\"{}\"", arg) } else { arg.clone() });
+    }
+    parts.join(" ")
+}
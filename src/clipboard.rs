@@ -0,0 +1,160 @@
+use crate::i18n::tf;
+use eframe::egui;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    pub enabled: bool,
+    /// Fetch formats right away instead of adding the link to the tray.
+    pub auto_fetch: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            auto_fetch: false,
+            patterns: default_patterns(),
+        }
+    }
+}
+
+pub fn default_patterns() -> Vec<String> {
+    [
+        r"https?://(www\.|m\.|music\.)?youtube\.com/(watch\?|shorts/|playlist\?|live/)\S+",
+        r"https?://youtu\.be/\S+",
+        r"https?://(www\.)?vimeo\.com/\d+\S*",
+        r"https?://(www\.)?soundcloud\.com/[\w\-]+/[\w\-]+\S*",
+        r"https?://(www\.)?(twitter|x)\.com/\w+/status/\d+\S*",
+        r"https?://(www\.)?tiktok\.com/@[\w.\-]+/video/\d+\S*",
+        r"https?://(www\.)?twitch\.tv/videos/\d+\S*",
+    ]
+    .iter()
+    .map(|pattern| pattern.to_string())
+    .collect()
+}
+
+/// Compiles the configured patterns, returning the first invalid one as an error.
+pub fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns
+        .iter()
+        .filter(|pattern| !pattern.trim().is_empty())
+        .map(|pattern| Regex::new(pattern.trim()).map_err(|e| tf("clipboard.invalid_pattern", &[("pattern", pattern), ("error", &e)])))
+        .collect()
+}
+
+/// The compiled patterns, recompiled only when the configured list changes.
+#[derive(Default)]
+pub struct PatternCache {
+    source: Vec<String>,
+    compiled: Option<Result<Vec<Regex>, String>>,
+}
+
+impl PatternCache {
+    pub fn get(&mut self, patterns: &[String]) -> &Result<Vec<Regex>, String> {
+        if self.source != patterns {
+            self.source = patterns.to_vec();
+            self.compiled = None;
+        }
+        self.compiled.get_or_insert_with(|| compile_patterns(patterns))
+    }
+}
+
+/// Every distinct match of `patterns` in `text`, in order of appearance.
+pub fn find_urls(text: &str, patterns: &[Regex]) -> Vec<String> {
+    let mut matches: Vec<(usize, String)> = patterns
+        .iter()
+        .flat_map(|pattern| pattern.find_iter(text))
+        .map(|m| (m.start(), m.as_str().to_string()))
+        .collect();
+    matches.sort_by_key(|(start, _)| *start);
+
+    let mut urls: Vec<String> = Vec::new();
+    for (_, url) in matches {
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+/// Polls the clipboard on the runtime until `running` is cleared and sends
+/// `CLIPBOARD:<text>` whenever its text content changes, waking the UI.
+pub fn spawn_watcher(runtime: &Runtime, tx: Sender<String>, running: Arc<AtomicBool>, ctx: egui::Context) {
+    runtime.spawn(async move {
+        let mut clipboard = match arboard::Clipboard::new() {
+            Ok(clipboard) => clipboard,
+            Err(e) => {
+                let _ = tx.send(format!("CLIPBOARD_ERROR:{}", tf("clipboard.unavailable", &[("error", &e)])));
+                return;
+            }
+        };
+        // Whatever is on the clipboard when watching starts is not new.
+        let mut last = clipboard.get_text().unwrap_or_default();
+
+        while running.load(Ordering::Relaxed) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if let Ok(text) = clipboard.get_text() {
+                if text != last {
+                    last = text.clone();
+                    if tx.send(format!("CLIPBOARD:{}", text)).is_err() {
+                        break;
+                    }
+                    ctx.request_repaint();
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<Regex> {
+        compile_patterns(&default_patterns()).unwrap()
+    }
+
+    #[test]
+    fn finds_supported_links_in_order() {
+        let text = "first https://vimeo.com/123 then https://youtu.be/abc\nand https://www.youtube.com/watch?v=xyz";
+        assert_eq!(
+            find_urls(text, &defaults()),
+            ["https://vimeo.com/123", "https://youtu.be/abc", "https://www.youtube.com/watch?v=xyz"]
+        );
+    }
+
+    #[test]
+    fn ignores_duplicates_and_other_sites() {
+        let text = "https://youtu.be/abc https://example.com/video https://youtu.be/abc https://www.youtube.com/@name";
+        assert_eq!(find_urls(text, &defaults()), ["https://youtu.be/abc"]);
+        assert!(find_urls("no links here", &defaults()).is_empty());
+    }
+
+    #[test]
+    fn skips_blank_patterns_and_reports_invalid_ones() {
+        let patterns = vec![" ".to_string(), r"https://a\.example/\d+".to_string()];
+        assert_eq!(compile_patterns(&patterns).unwrap().len(), 1);
+        assert!(compile_patterns(&["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn cache_recompiles_when_patterns_change() {
+        let mut cache = PatternCache::default();
+        let first = vec![r"https://a\.example/\d+".to_string()];
+        assert_eq!(find_urls("https://a.example/1", cache.get(&first).as_ref().unwrap()), ["https://a.example/1"]);
+        let second = vec![r"https://b\.example/\d+".to_string()];
+        assert!(find_urls("https://a.example/1", cache.get(&second).as_ref().unwrap()).is_empty());
+        assert!(cache.get(&["(".to_string()]).is_err());
+    }
+}
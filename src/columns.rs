@@ -0,0 +1,78 @@
+use crate::i18n::t;
+use crate::{short_codec, FormatInfo};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FormatColumn {
+    Select,
+    Id,
+    Type,
+    Resolution,
+    Video,
+    Audio,
+}
+
+impl FormatColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FormatColumn::Select => t("columns.select"),
+            FormatColumn::Id => t("columns.id"),
+            FormatColumn::Type => t("columns.type"),
+            FormatColumn::Resolution => t("columns.resolution"),
+            FormatColumn::Video => t("columns.video"),
+            FormatColumn::Audio => t("columns.audio"),
+        }
+    }
+
+    /// Text shown for `format`; the select column renders its own widget.
+    pub fn cell_text(&self, format: &FormatInfo) -> String {
+        match self {
+            FormatColumn::Select => String::new(),
+            FormatColumn::Id => format.format_id.clone(),
+            FormatColumn::Type => format.ext.clone(),
+            FormatColumn::Resolution => format.resolution.clone().unwrap_or_else(|| t("columns.not_available").to_string()),
+            FormatColumn::Video => short_codec(&format.vcodec),
+            FormatColumn::Audio => format.acodec.clone().unwrap_or_else(|| t("columns.not_available").to_string()),
+        }
+    }
+}
+
+/// Position, visibility and width of one format table column.
+/// The order of the list in the config is the display order.
+/// Missing fields take these defaults; a missing `column` makes the list
+/// incomplete, which config validation replaces with the default layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnSetting {
+    pub column: FormatColumn,
+    pub visible: bool,
+    pub width: f32,
+}
+
+impl Default for ColumnSetting {
+    fn default() -> Self {
+        Self {
+            column: FormatColumn::Select,
+            visible: true,
+            width: 72.0,
+        }
+    }
+}
+
+pub fn default_columns() -> Vec<ColumnSetting> {
+    [
+        (FormatColumn::Select, 48.0),
+        (FormatColumn::Id, 64.0),
+        (FormatColumn::Type, 56.0),
+        (FormatColumn::Resolution, 96.0),
+        (FormatColumn::Video, 72.0),
+        (FormatColumn::Audio, 72.0),
+    ]
+    .into_iter()
+    .map(|(column, width)| ColumnSetting {
+        column,
+        visible: true,
+        width,
+    })
+    .collect()
+}
@@ -1,65 +1,560 @@
+use crate::api::ApiConfig;
+use crate::auth::AuthProfile;
+use crate::clipboard::ClipboardConfig;
+use crate::columns::{default_columns, ColumnSetting};
+use crate::i18n::{self, t, tf, Language};
+use crate::info_cache::MAX_TTL_MINUTES;
+use crate::network::NetworkConfig;
+use crate::notifications::NotificationConfig;
+use crate::presets::{default_presets, Preset};
+use crate::schedule::ScheduleConfig;
+use crate::shortcuts::{complete_bindings, default_bindings, Binding, Trigger};
+use crate::theme::ThemeMode;
+use crate::ytdlp::UpdateChannel;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Serialize, Deserialize)]
+/// Bump together with a new step in [`Config::migrate`].
+pub const CONFIG_VERSION: u32 = 2;
+
+/// A file with this name next to the executable keeps all data beside it.
+const PORTABLE_MARKER: &str = concat!(env!("APP_NAME_LOWER"), ".portable");
+
+/// Set from `--config <path>` before the config is first loaded.
+static CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// A named copy of all settings, switchable from the top bar.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub settings: Config,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
+    pub version: u32,
     pub yt_dlp_path: String,
+    pub yt_dlp_channel: UpdateChannel,
+    /// Empty means auto-detect next to the executable or on PATH.
+    pub ffmpeg_path: String,
     pub download_dir: String,
+    pub auth_profiles: Vec<AuthProfile>,
+    pub network: NetworkConfig,
+    pub schedule: ScheduleConfig,
+    pub clipboard: ClipboardConfig,
+    pub notifications: NotificationConfig,
+    /// Shell command run after each downloaded file, with `{path}`, `{title}`
+    /// and `{url}` placeholders.
+    pub post_download_command: String,
+    /// How long fetched info JSON is reused, in minutes; 0 disables the cache.
+    pub info_cache_minutes: u32,
+    pub api: ApiConfig,
+    pub presets: Vec<Preset>,
+    pub profiles: Vec<SettingsProfile>,
+    /// Name of the profile last applied; empty when none is.
+    pub active_profile: String,
     // UI Constants
     pub row_height: f32,
     pub spacing: f32,
     pub margin: f32,
     pub padding: f32,
     pub icon_button_size: f32,
+    pub theme: ThemeMode,
+    pub accent_color: [u8; 3],
+    pub font_scale: f32,
+    pub language: Language,
+    /// Text buttons instead of emoji icons.
+    pub text_labels: bool,
+    pub high_contrast: bool,
+    /// Format table columns in display order.
+    pub format_columns: Vec<ColumnSetting>,
+    /// Keyboard shortcuts, one per action.
+    pub shortcuts: Vec<Binding>,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let exe_dir = std::env::current_exe()
-            .unwrap_or_else(|_| PathBuf::from("."))
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("."))
-            .to_path_buf();
+        let exe_dir = exe_dir();
+        let download_dir = if Self::is_portable() {
+            exe_dir.clone()
+        } else {
+            dirs::download_dir().unwrap_or_else(|| exe_dir.clone())
+        };
 
         Self {
+            version: CONFIG_VERSION,
             yt_dlp_path: exe_dir.join("yt-dlp.exe").to_string_lossy().to_string(),
-            download_dir: exe_dir.to_string_lossy().to_string(),
+            yt_dlp_channel: UpdateChannel::default(),
+            ffmpeg_path: String::new(),
+            download_dir: download_dir.to_string_lossy().to_string(),
+            auth_profiles: Vec::new(),
+            network: NetworkConfig::default(),
+            schedule: ScheduleConfig::default(),
+            clipboard: ClipboardConfig::default(),
+            notifications: NotificationConfig::default(),
+            post_download_command: String::new(),
+            info_cache_minutes: 60,
+            api: ApiConfig::default(),
+            presets: default_presets(),
+            profiles: Vec::new(),
+            active_profile: String::new(),
             // Default UI values
             row_height: 28.0,
             spacing: 2.0,
             margin: 8.0,
             padding: 8.0,
             icon_button_size: 28.0,
+            theme: ThemeMode::default(),
+            accent_color: [0, 92, 128],
+            font_scale: 1.0,
+            language: Language::default(),
+            text_labels: false,
+            high_contrast: false,
+            format_columns: default_columns(),
+            shortcuts: default_bindings(),
         }
     }
 }
 
 impl Config {
-    pub fn load() -> Self {
+    /// Loads the config, migrating older versions and clamping out-of-range
+    /// values. Problems are returned so the UI can show them; an unreadable
+    /// file is backed up instead of being overwritten with defaults.
+    pub fn load() -> (Self, Vec<String>) {
+        let _ = fs::create_dir_all(Self::get_config_dir());
+        Self::import_legacy_files();
         let config_path = Self::get_config_path();
-        if let Ok(contents) = fs::read_to_string(&config_path) {
-            if let Ok(config) = serde_json::from_str(&contents) {
-                return config;
+        let contents = match fs::read_to_string(&config_path) {
+            Ok(contents) => contents,
+            Err(_) => {
+                let default_config = Self::default();
+                let _ = default_config.save();
+                return (default_config, Vec::new());
+            }
+        };
+
+        let mut errors = Vec::new();
+        match Self::parse(&contents, &mut errors) {
+            Ok(config) => (config, errors),
+            Err(e) => {
+                let backup = config_path.with_extension(format!(
+                    "json.bak-{}",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                ));
+                match fs::copy(&config_path, &backup) {
+                    Ok(_) => errors.push(tf(
+                        "config.read_failed",
+                        &[("error", &e), ("backup", &backup.display())],
+                    )),
+                    Err(copy_error) => errors.push(tf(
+                        "config.backup_failed",
+                        &[("error", &e), ("backup_error", &copy_error)],
+                    )),
+                }
+                (Self::default(), errors)
+            }
+        }
+    }
+
+    /// Parses, migrates and validates a config document.
+    fn parse(contents: &str, errors: &mut Vec<String>) -> Result<Self, String> {
+        let mut value = serde_json::from_str::<Value>(contents).map_err(|e| e.to_string())?;
+        Self::migrate(&mut value)?;
+        let mut config = serde_json::from_value::<Self>(value).map_err(|e| e.to_string())?;
+        // Problems are reported in the configured language.
+        i18n::set_language(config.language);
+        config.validate(errors);
+        Ok(config)
+    }
+
+    /// Reads a config exported with [`Config::export`]. Problems that could be
+    /// fixed (old version, out-of-range values) are returned as warnings.
+    /// Tool and download paths stay those of `current` unless the file names
+    /// ones that exist on this machine.
+    pub fn import(path: &Path, current: &Config) -> Result<(Self, Vec<String>), String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut errors = Vec::new();
+        let mut config = Self::parse(&contents, &mut errors)?;
+        config.adopt_machine_paths(current, &mut errors);
+        Ok((config, errors))
+    }
+
+    /// Writes the settings to `path`. Credentials live in the secrets file
+    /// and are never part of the export, and neither are the paths, which
+    /// only make sense on this machine.
+    pub fn export(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = self.clone();
+        config.clear_machine_paths();
+        for profile in &mut config.profiles {
+            profile.settings.clear_machine_paths();
+        }
+        let json = serde_json::to_string_pretty(&config)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Stores the current settings under `name`, replacing a profile of the same name.
+    /// Paths are left out, so switching profiles keeps the current tools and folder.
+    pub fn save_profile(&mut self, name: &str) {
+        let mut settings = self.clone();
+        settings.profiles.clear();
+        settings.active_profile.clear();
+        settings.clear_machine_paths();
+
+        match self.profiles.iter_mut().find(|profile| profile.name == name) {
+            Some(profile) => profile.settings = settings,
+            None => self.profiles.push(SettingsProfile {
+                name: name.to_string(),
+                settings,
+            }),
+        }
+        self.active_profile = name.to_string();
+    }
+
+    /// Replaces the current settings with those of profile `name`. Paths the
+    /// profile names that do not exist here are reported and not applied.
+    pub fn apply_profile(&mut self, name: &str, errors: &mut Vec<String>) -> bool {
+        let Some(profile) = self.profiles.iter().find(|profile| profile.name == name) else {
+            return false;
+        };
+        let mut settings = profile.settings.clone();
+        settings.adopt_machine_paths(self, errors);
+        settings.profiles = std::mem::take(&mut self.profiles);
+        settings.active_profile = name.to_string();
+        settings.version = CONFIG_VERSION;
+        *self = settings;
+        true
+    }
+
+    fn clear_machine_paths(&mut self) {
+        self.yt_dlp_path.clear();
+        self.ffmpeg_path.clear();
+        self.download_dir.clear();
+    }
+
+    /// Keeps the paths of `current` wherever these settings have none or
+    /// name one that does not exist on this machine. Bare program names are
+    /// looked up on `PATH` and kept as they are.
+    fn adopt_machine_paths(&mut self, current: &Config, errors: &mut Vec<String>) {
+        let paths = [
+            (t("paths.yt_dlp"), &mut self.yt_dlp_path, &current.yt_dlp_path),
+            (t("paths.ffmpeg"), &mut self.ffmpeg_path, &current.ffmpeg_path),
+            (t("paths.download_dir"), &mut self.download_dir, &current.download_dir),
+        ];
+        for (name, path, current) in paths {
+            if path.is_empty() {
+                path.clone_from(current);
+            } else if Path::new(path.as_str()).components().count() > 1 && !Path::new(path.as_str()).exists() {
+                errors.push(tf("config.path_missing", &[("name", &name.trim_end_matches(':')), ("path", path)]));
+                path.clone_from(current);
+            }
+        }
+    }
+
+    pub fn delete_profile(&mut self, name: &str) {
+        self.profiles.retain(|profile| profile.name != name);
+        if self.active_profile == name {
+            self.active_profile.clear();
+        }
+    }
+
+    /// Upgrades a raw config document step by step to [`CONFIG_VERSION`].
+    fn migrate(value: &mut Value) -> Result<(), String> {
+        let object = value.as_object_mut().ok_or_else(|| t("config.not_object").to_string())?;
+        let mut version = object.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+
+        // Saving a newer file with this build would silently drop its settings;
+        // failing here backs it up instead.
+        if version > CONFIG_VERSION {
+            return Err(tf("config.newer_version", &[("version", &version)]));
+        }
+
+        while version < CONFIG_VERSION {
+            match version {
+                // Unversioned files: fields added since then are filled in
+                // from defaults by serde, nothing to rename.
+                0 => {}
+                // "Ctrl+," never fired on Windows and macOS; see `shortcuts::Trigger`.
+                1 => {
+                    if let Some(bindings) = object.get_mut("shortcuts").and_then(Value::as_array_mut) {
+                        for binding in bindings {
+                            if binding["action"] == "ToggleSettings" && binding["keys"] == "Ctrl+," {
+                                binding["keys"] = Value::from("Ctrl+Shift+S");
+                            }
+                        }
+                    }
+                }
+                _ => return Err(tf("config.no_migration", &[("version", &version)])),
+            }
+            version += 1;
+        }
+        object.insert("version".to_string(), Value::from(CONFIG_VERSION));
+        Ok(())
+    }
+
+    /// Clamps values to the ranges the settings UI allows.
+    fn validate(&mut self, errors: &mut Vec<String>) {
+        clamp_setting(t("settings.row_height"), &mut self.row_height, 20.0..=40.0, errors);
+        clamp_setting(t("settings.spacing"), &mut self.spacing, 0.0..=10.0, errors);
+        clamp_setting(t("settings.margin"), &mut self.margin, 0.0..=20.0, errors);
+        clamp_setting(t("settings.padding"), &mut self.padding, 0.0..=20.0, errors);
+        clamp_setting(t("settings.icon_size"), &mut self.icon_button_size, 20.0..=40.0, errors);
+        clamp_setting(t("settings.font_scale"), &mut self.font_scale, 0.75..=2.0, errors);
+        clamp_setting(t("network.retries"), &mut self.network.retries, 0..=100, errors);
+        clamp_setting(t("network.fragment_retries"), &mut self.network.fragment_retries, 0..=100, errors);
+        clamp_setting(t("network.socket_timeout"), &mut self.network.socket_timeout, 0..=600, errors);
+        clamp_setting(t("network.concurrent_fragments"), &mut self.network.concurrent_fragments, 1..=16, errors);
+        clamp_setting(t("config.info_cache"), &mut self.info_cache_minutes, 0..=MAX_TTL_MINUTES, errors);
+        clamp_setting(t("config.schedule_start"), &mut self.schedule.window_start, 0..=1439, errors);
+        clamp_setting(t("config.schedule_end"), &mut self.schedule.window_end, 0..=1439, errors);
+        for column in &mut self.format_columns {
+            clamp_setting(column.column.label(), &mut column.width, 24.0..=600.0, errors);
+        }
+
+        self.presets.retain(|preset| !preset.name.trim().is_empty() && !preset.format.trim().is_empty());
+        if self.presets.is_empty() {
+            errors.push(t("config.no_presets").to_string());
+            self.presets = default_presets();
+        }
+
+        let defaults = default_columns();
+        let complete = self.format_columns.len() == defaults.len()
+            && defaults.iter().all(|default| {
+                self.format_columns.iter().filter(|c| c.column == default.column).count() == 1
+            });
+        if !complete {
+            errors.push(t("config.columns_incomplete").to_string());
+            self.format_columns = defaults;
+        }
+
+        complete_bindings(&mut self.shortcuts);
+        for binding in &mut self.shortcuts {
+            if let Err(e) = Trigger::parse(&binding.keys) {
+                errors.push(tf("config.shortcut_removed", &[("error", &e), ("action", &binding.action.label())]));
+                binding.keys.clear();
             }
         }
-        let default_config = Self::default();
-        let _ = default_config.save();
-        default_config
     }
 
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let config_path = Self::get_config_path();
+        if let Some(dir) = config_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
         let json = serde_json::to_string_pretty(self)?;
         fs::write(config_path, json)?;
         Ok(())
     }
 
-    fn get_config_path() -> PathBuf {
-        let exe_dir = std::env::current_exe()
-            .map(|p| p.parent().unwrap_or_else(|| std::path::Path::new(".")).to_path_buf())
-            .unwrap_or_else(|_| PathBuf::from("."));
-        
-        exe_dir.join(format!("{}.json", env!("APP_NAME_LOWER")))
+    /// Uses `path` instead of the default config file; secrets and the queue
+    /// are stored next to it. Must be called before the first [`Config::load`].
+    pub fn set_config_file(path: PathBuf) {
+        let _ = CONFIG_FILE_OVERRIDE.set(path);
+    }
+
+    pub fn is_portable() -> bool {
+        exe_dir().join(PORTABLE_MARKER).is_file()
+    }
+
+    pub fn get_config_path() -> PathBuf {
+        match CONFIG_FILE_OVERRIDE.get() {
+            Some(path) => path.clone(),
+            None => Self::get_config_dir().join(format!("{}.json", env!("APP_NAME_LOWER"))),
+        }
+    }
+
+    /// Credentials are kept out of the main config file.
+    pub fn get_secrets_path() -> PathBuf {
+        Self::get_config_dir().join(format!("{}.secrets.json", env!("APP_NAME_LOWER")))
+    }
+
+    pub fn get_queue_path() -> PathBuf {
+        Self::get_config_dir().join(format!("{}.queue.json", env!("APP_NAME_LOWER")))
+    }
+
+    pub fn get_cache_dir() -> PathBuf {
+        Self::get_config_dir().join("cache")
+    }
+
+    /// The `--config` file's folder, the executable's folder in portable mode,
+    /// otherwise the platform config dir (`%APPDATA%`, `$XDG_CONFIG_HOME`, ...).
+    pub fn get_config_dir() -> PathBuf {
+        if let Some(path) = CONFIG_FILE_OVERRIDE.get() {
+            return path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        }
+        if Self::is_portable() {
+            return exe_dir();
+        }
+
+        #[cfg(any(windows, target_os = "macos"))]
+        let folder = env!("APP_NAME");
+        #[cfg(not(any(windows, target_os = "macos")))]
+        let folder = env!("APP_NAME_LOWER");
+
+        dirs::config_dir()
+            .map(|dir| dir.join(folder))
+            .unwrap_or_else(exe_dir)
+    }
+
+    /// Earlier versions kept their files next to the executable. Copy them
+    /// over once so upgrading does not lose settings, credentials or jobs.
+    fn import_legacy_files() {
+        let config_dir = Self::get_config_dir();
+        if CONFIG_FILE_OVERRIDE.get().is_some() || config_dir == exe_dir() {
+            return;
+        }
+        for target in [Self::get_config_path(), Self::get_secrets_path(), Self::get_queue_path()] {
+            let Some(name) = target.file_name() else {
+                continue;
+            };
+            let legacy = exe_dir().join(name);
+            if legacy.is_file() && !target.exists() {
+                let _ = fs::copy(&legacy, &target);
+            }
+        }
+    }
+}
+
+/// Writes a file only the current user can read. On Unix it is created with
+/// mode 0600 under a temporary name and renamed into place, so the contents
+/// are never readable by others, not even briefly.
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        let _ = fs::remove_file(&temp);
+        let mut file = fs::OpenOptions::new().write(true).create_new(true).mode(0o600).open(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    }
+    #[cfg(not(unix))]
+    {
+        fs::write(path, contents)
+    }
+}
+
+fn exe_dir() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn clamp_setting<T>(name: &str, value: &mut T, range: RangeInclusive<T>, errors: &mut Vec<String>)
+where
+    T: PartialOrd + Copy + std::fmt::Display,
+{
+    let clamped = if *value < *range.start() {
+        *range.start()
+    } else if *value > *range.end() {
+        *range.end()
+    } else {
+        return;
+    };
+    errors.push(tf(
+        "config.out_of_range",
+        &[
+            ("name", &name.trim_end_matches(':')),
+            ("value", value),
+            ("min", range.start()),
+            ("max", range.end()),
+            ("clamped", &clamped),
+        ],
+    ));
+    *value = clamped;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let mut value = json!({ "yt_dlp_path": "/usr/bin/yt-dlp", "row_height": 30.0 });
+        Config::migrate(&mut value).unwrap();
+        assert_eq!(value["version"], json!(CONFIG_VERSION));
+
+        let mut errors = Vec::new();
+        let config = Config::parse(&value.to_string(), &mut errors).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(config.yt_dlp_path, "/usr/bin/yt-dlp");
+        assert_eq!(config.row_height, 30.0);
+        assert_eq!(config.info_cache_minutes, Config::default().info_cache_minutes);
+    }
+
+    #[test]
+    fn keeps_current_files() {
+        let mut value = json!({ "version": CONFIG_VERSION, "padding": 4.0 });
+        Config::migrate(&mut value).unwrap();
+        assert_eq!(value, json!({ "version": CONFIG_VERSION, "padding": 4.0 }));
+    }
+
+    #[test]
+    fn migrates_v1_settings_shortcut() {
+        let mut value = json!({
+            "version": 1,
+            "shortcuts": [
+                { "action": "ToggleSettings", "keys": "Ctrl+," },
+                { "action": "Cancel", "keys": "Ctrl+," },
+            ],
+        });
+        Config::migrate(&mut value).unwrap();
+        assert_eq!(value["shortcuts"][0]["keys"], "Ctrl+Shift+S");
+        assert_eq!(value["shortcuts"][1]["keys"], "Ctrl+,");
+    }
+
+    #[test]
+    fn refuses_newer_and_malformed_files() {
+        assert!(Config::migrate(&mut json!({ "version": CONFIG_VERSION + 1 })).is_err());
+        assert!(Config::migrate(&mut json!([1, 2])).is_err());
+        assert!(Config::parse("{ not json", &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn clamps_out_of_range_values() {
+        let mut errors = Vec::new();
+        let config = Config::parse(r#"{ "version": 1, "row_height": 500.0, "info_cache_minutes": 100000 }"#, &mut errors).unwrap();
+        assert_eq!(config.row_height, 40.0);
+        assert_eq!(config.info_cache_minutes, MAX_TTL_MINUTES);
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn fills_missing_column_fields() {
+        let mut errors = Vec::new();
+        let columns: Vec<Value> = default_columns()
+            .iter()
+            .map(|column| json!({ "column": column.column, "width": 100.0 }))
+            .collect();
+        let config = Config::parse(&json!({ "version": 1, "format_columns": columns }).to_string(), &mut errors).unwrap();
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(config.format_columns.iter().all(|column| column.visible && column.width == 100.0));
+    }
+
+    #[test]
+    fn replaces_unusable_presets_and_columns() {
+        let mut errors = Vec::new();
+        let config = Config::parse(
+            r#"{ "version": 1, "presets": [{ "name": " ", "format": "best" }], "format_columns": [] }"#,
+            &mut errors,
+        )
+        .unwrap();
+        assert_eq!(config.presets.len(), default_presets().len());
+        assert_eq!(config.format_columns.len(), default_columns().len());
+        assert_eq!(errors.len(), 2);
     }
 }
@@ -0,0 +1,136 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+/// Opens a file or folder with the default application of the desktop.
+pub fn open_path(path: &Path) -> io::Result<()> {
+    #[cfg(target_os = "windows")]
+    let mut command = Command::new("explorer");
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    command.arg(path).spawn().map(|_| ())
+}
+
+/// Shows the file selected in the file manager. Linux file managers have no
+/// common way to select a file, so its folder is opened instead.
+pub fn reveal_path(path: &Path) -> io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(path);
+        Command::new("explorer").arg(select).spawn().map(|_| ())
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg("-R").arg(path).spawn().map(|_| ())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        open_path(path.parent().unwrap_or(path))
+    }
+}
+
+/// Replaces `{name}` placeholders with shell-quoted values, so templates
+/// like `ffplay {path}` work with spaces and quotes in titles.
+pub fn expand_command(template: &str, values: &[(&str, &str)]) -> String {
+    let mut command = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        command.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            values.iter().find(|(key, _)| *key == name).map(|(_, value)| (end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                command.push_str(&shell_quote(value));
+                rest = &rest[end + 1..];
+            }
+            None => {
+                command.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    command.push_str(rest);
+    command
+}
+
+/// cmd expands `%VAR%` and `!VAR!` even inside quotes, so those characters
+/// briefly leave the quotes to be escaped with `^`. cmd has no escape for
+/// quotes inside quotes; they cannot occur in paths.
+#[cfg(target_os = "windows")]
+fn shell_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push('\''),
+            '%' | '!' => {
+                quoted.push_str("\"^");
+                quoted.push(c);
+                quoted.push('"');
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Runs a command line through the platform shell and waits for it.
+pub fn run_shell(command: &str) -> io::Result<ExitStatus> {
+    // Passed raw: std would escape inner quotes as `\"`, which cmd does not
+    // understand. `/S` makes cmd strip exactly the outer pair of quotes.
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        Command::new("cmd").arg("/S").arg("/C").raw_arg(format!("\"{}\"", command)).status()
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Command::new("sh").arg("-c").arg(command).status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_unknown_placeholders_alone() {
+        assert_eq!(expand_command("echo {other} {", &[("path", "a")]), "echo {other} {");
+        assert_eq!(expand_command("echo {}", &[]), "echo {}");
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn quotes_values_for_sh() {
+        assert_eq!(expand_command("mpv {path}", &[("path", "/tmp/a b.mkv")]), "mpv '/tmp/a b.mkv'");
+        assert_eq!(expand_command("echo {title}", &[("title", "it's")]), "echo 'it'\\''s'");
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn values_reach_the_command_unchanged() {
+        let title = "a 'b' \"c\" $HOME `id` ; | & {path} \\ %d !";
+        let command = expand_command("printf %s {title}", &[("title", title), ("path", "x")]);
+        let output = Command::new("sh").arg("-c").arg(command).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), title);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn quotes_values_for_cmd() {
+        assert_eq!(expand_command("start {path}", &[("path", r"C:\a b.mkv")]), r#"start "C:\a b.mkv""#);
+        assert_eq!(expand_command("echo {title}", &[("title", "100% \"a\"!")]), r#"echo "100"^%" 'a'"^!"""#);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::i18n::{t, tf};
+use crate::ytdlp::find_on_path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(windows)]
+const FFMPEG_BINARY: &str = "ffmpeg.exe";
+#[cfg(not(windows))]
+const FFMPEG_BINARY: &str = "ffmpeg";
+
+/// Runs `<path> -version` and returns the version token from the first line,
+/// e.g. `6.1.1` from `ffmpeg version 6.1.1 Copyright ...`.
+pub fn probe_version(path: &str) -> Result<String, String> {
+    let output = Command::new(path)
+        .arg("-version")
+        .output()
+        .map_err(|e| tf("errors.cannot_run", &[("path", &path), ("error", &e)]))?;
+
+    if !output.status.success() {
+        return Err(tf("errors.exited_with", &[("path", &path), ("status", &output.status)]));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next().unwrap_or("");
+    first_line
+        .split_whitespace()
+        .skip_while(|word| *word != "version")
+        .nth(1)
+        .map(|version| version.to_string())
+        .ok_or_else(|| tf("errors.no_version", &[("path", &path)]))
+}
+
+fn candidates(configured: &str) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if !configured.trim().is_empty() {
+        candidates.push(PathBuf::from(configured.trim()));
+    }
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|p| p.parent().map(Path::to_path_buf)) {
+        candidates.push(exe_dir.join(FFMPEG_BINARY));
+    }
+    if let Some(found) = find_on_path(FFMPEG_BINARY) {
+        candidates.push(found);
+    }
+    candidates
+}
+
+/// Finds a working ffmpeg: the configured path first (empty means auto-detect),
+/// then next to the executable, then on `PATH`. Returns the path and its version.
+pub fn resolve(configured: &str) -> Result<(String, String), String> {
+    for candidate in candidates(configured) {
+        if !candidate.is_file() {
+            continue;
+        }
+        let path = candidate.to_string_lossy().to_string();
+        if let Ok(version) = probe_version(&path) {
+            return Ok((path, version));
+        }
+    }
+
+    if configured.trim().is_empty() {
+        Err(t("errors.ffmpeg_not_found").to_string())
+    } else {
+        Err(tf("errors.ffmpeg_not_found_at", &[("path", &configured.trim())]))
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Name of the language in the language itself, for the selector.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn catalog_source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../res/locales/en.json"),
+            Language::German => include_str!("../res/locales/de.json"),
+        }
+    }
+
+    /// CLDR plural category of `count`, used as the key suffix for counted messages.
+    fn plural_category(&self, count: u64) -> &'static str {
+        match self {
+            Language::English | Language::German => {
+                if count == 1 {
+                    "one"
+                } else {
+                    "other"
+                }
+            }
+        }
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+static CATALOGS: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL
+        .get(LANGUAGE.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn catalog(language: Language) -> &'static HashMap<String, String> {
+    let catalogs = CATALOGS.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|language| serde_json::from_str(language.catalog_source()).unwrap_or_default())
+            .collect()
+    });
+    &catalogs[language as usize]
+}
+
+/// The message for `key` in the current language, falling back to English
+/// and then to the key itself so a missing entry is visible but harmless.
+pub fn t(key: &str) -> &str {
+    catalog(language())
+        .get(key)
+        .or_else(|| catalog(Language::English).get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Like [`t`], replacing `{name}` placeholders with the given arguments.
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut message = t(key).to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+/// Counted message: looks up `key.one`, `key.other`, … by the plural rules of
+/// the current language and fills in `{count}` along with `args`.
+pub fn tn(key: &str, count: usize, args: &[(&str, &dyn Display)]) -> String {
+    let category = language().plural_category(count as u64);
+    tf(&format!("{}.{}", key, category), args).replace("{count}", &count.to_string())
+}
@@ -0,0 +1,79 @@
+//! yt-dlp's info JSON of recently fetched URLs, so format lists reopen
+//! instantly and downloads can skip a second extraction via `--load-info-json`.
+//! Entries are keyed by URL together with the login and network settings
+//! used, since the JSON carries per-format headers and cookies.
+
+use crate::config::{self, Config};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Longest allowed TTL in minutes. Signed format URLs in the JSON expire
+/// (after about six hours on YouTube); entries must be gone before that.
+pub const MAX_TTL_MINUTES: u32 = 240;
+
+/// Folder holding the cache entries.
+fn cache_dir() -> PathBuf {
+    Config::get_cache_dir().join("info")
+}
+
+/// One file per cache key.
+pub fn path_for(key: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    cache_dir().join(format!("{:016x}.info.json", hasher.finish()))
+}
+
+/// Age of the entry for `key` if it is younger than `ttl`.
+pub fn age(key: &str, ttl: Duration) -> Option<Duration> {
+    let modified = fs::metadata(path_for(key)).and_then(|metadata| metadata.modified()).ok()?;
+    let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+    (age < ttl).then_some(age)
+}
+
+/// The cached JSON for `key` if it is younger than `ttl`.
+pub fn load(key: &str, ttl: Duration) -> Option<(String, Duration)> {
+    let age = age(key, ttl)?;
+    let json = fs::read_to_string(path_for(key)).ok()?;
+    Some((json, age))
+}
+
+/// The cache file to pass to `--load-info-json`, if still fresh.
+pub fn fresh_path(key: &str, ttl: Duration) -> Option<PathBuf> {
+    age(key, ttl).map(|_| path_for(key))
+}
+
+/// Saves `json` for `key`, readable by the current user only, dropping
+/// entries older than `ttl` on the way.
+pub fn store(key: &str, json: &str, ttl: Duration) -> io::Result<()> {
+    fs::create_dir_all(cache_dir())?;
+    for entry in fs::read_dir(cache_dir())?.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .map(|modified| SystemTime::now().duration_since(modified).unwrap_or_default() >= ttl)
+            .unwrap_or(false);
+        if expired {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+    config::write_private(&path_for(key), json.as_bytes())
+}
+
+pub fn remove(key: &str) {
+    let _ = fs::remove_file(path_for(key));
+}
+
+/// Deletes every entry; returns how many there were.
+pub fn clear() -> usize {
+    let Ok(entries) = fs::read_dir(cache_dir()) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| fs::remove_file(entry.path()).is_ok())
+        .count()
+}
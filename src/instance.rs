@@ -0,0 +1,189 @@
+use crate::config::{self, Config};
+use eframe::egui;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener as Listener, UnixStream as Stream};
+#[cfg(not(unix))]
+use std::net::{TcpListener as Listener, TcpStream as Stream};
+
+/// First line of every handoff, and the primary's answer to it.
+const HANDSHAKE: &str = concat!(env!("APP_NAME_LOWER"), "-instance-1");
+/// How long a later launch waits for the primary to start listening.
+const CONNECT_ATTEMPTS: u32 = 20;
+const CONNECT_DELAY: Duration = Duration::from_millis(100);
+
+/// Result of [`acquire`]: either this process owns the config and must start
+/// the window, or its links were handed to the process that already does.
+pub enum Instance {
+    Primary(InstanceListener),
+    Forwarded,
+    /// Another process holds the instance lock but did not take the links.
+    Unreachable(io::Error),
+}
+
+/// The socket other launches connect to; one per config file, so separate
+/// `--config` setups can still run side by side. Holds the instance lock for
+/// the lifetime of the process.
+pub struct InstanceListener {
+    listener: Listener,
+    token: String,
+    _lock: File,
+}
+
+fn sibling_path(suffix: &str) -> PathBuf {
+    let mut path = Config::get_config_path().into_os_string();
+    path.push(suffix);
+    path.into()
+}
+
+/// Hands `links` to a running instance, or becomes the running instance.
+///
+/// The exclusive lock on `<config>.lock` decides who is primary, so two
+/// launches at the same moment cannot both start a window. Errors mean the
+/// lock itself is unusable and the app runs without single-instance mode.
+pub fn acquire(links: &[String]) -> io::Result<Instance> {
+    let lock_path = sibling_path(".lock");
+    // Runs before `Config::load`, which would otherwise create the folder.
+    if let Some(dir) = lock_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let lock = fs::OpenOptions::new().create(true).truncate(false).write(true).open(lock_path)?;
+    match lock.try_lock() {
+        Ok(()) => {
+            // Only readable by this user, so other users cannot inject links.
+            let token = crate::api::generate_token()?;
+            config::write_private(&sibling_path(".token"), token.as_bytes())?;
+            let listener = bind()?;
+            Ok(Instance::Primary(InstanceListener { listener, token, _lock: lock }))
+        }
+        Err(fs::TryLockError::WouldBlock) => {
+            let mut last_error = io::Error::new(io::ErrorKind::NotConnected, "no answer");
+            for _ in 0..CONNECT_ATTEMPTS {
+                match forward(links) {
+                    Handoff::Delivered => return Ok(Instance::Forwarded),
+                    Handoff::Rejected(e) => return Ok(Instance::Unreachable(e)),
+                    Handoff::NotSent(e) => last_error = e,
+                }
+                std::thread::sleep(CONNECT_DELAY);
+            }
+            Ok(Instance::Unreachable(last_error))
+        }
+        Err(fs::TryLockError::Error(e)) => Err(e),
+    }
+}
+
+/// Outcome of one attempt to hand links to the primary.
+enum Handoff {
+    Delivered,
+    /// Nothing reached the primary yet; trying again may succeed.
+    NotSent(io::Error),
+    /// The links were sent but whatever listens there is not the primary.
+    Rejected(io::Error),
+}
+
+/// Sends the handshake, the token and the links, then waits for the primary
+/// to echo the handshake; anything else listening there does not count. Once
+/// the links are written they are never sent again, so a slow answer counts
+/// as delivered rather than risking a second copy.
+fn forward(links: &[String]) -> Handoff {
+    let send = || -> io::Result<Stream> {
+        let token = fs::read_to_string(sibling_path(".token"))?;
+        let stream = connect()?;
+        stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+        stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+        let mut writer = &stream;
+        writeln!(writer, "{}", HANDSHAKE)?;
+        writeln!(writer, "{}", token.trim())?;
+        for link in links {
+            writeln!(writer, "{}", link.trim())?;
+        }
+        writer.flush()?;
+        stream.shutdown(Shutdown::Write)?;
+        Ok(stream)
+    };
+    let stream = match send() {
+        Ok(stream) => stream,
+        Err(e) => return Handoff::NotSent(e),
+    };
+
+    let mut answer = String::new();
+    match BufReader::new(&stream).read_line(&mut answer) {
+        Ok(_) if answer.trim() == HANDSHAKE => Handoff::Delivered,
+        Ok(_) => Handoff::Rejected(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected answer from the running instance",
+        )),
+        Err(_) => Handoff::Delivered,
+    }
+}
+
+#[cfg(unix)]
+fn connect() -> io::Result<Stream> {
+    Stream::connect(sibling_path(".sock"))
+}
+
+/// Called with the lock held. A socket file nobody answers on is left over
+/// from a crashed run and is replaced; a live one is never removed.
+#[cfg(unix)]
+fn bind() -> io::Result<Listener> {
+    let path = sibling_path(".sock");
+    match Listener::bind(&path) {
+        Err(e) if e.kind() == io::ErrorKind::AddrInUse => match Stream::connect(&path) {
+            Err(refused) if refused.kind() == io::ErrorKind::ConnectionRefused => {
+                fs::remove_file(&path)?;
+                Listener::bind(&path)
+            }
+            _ => Err(e),
+        },
+        result => result,
+    }
+}
+
+/// std has no named pipes on Windows; a loopback port derived from the
+/// config path serves instead. The answered handshake and the token from
+/// the user's profile keep unrelated listeners and other users out.
+#[cfg(not(unix))]
+fn port() -> u16 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    Config::get_config_path().hash(&mut hasher);
+    49152 + (hasher.finish() % 16384) as u16
+}
+
+#[cfg(not(unix))]
+fn connect() -> io::Result<Stream> {
+    let address = std::net::SocketAddr::from((std::net::Ipv4Addr::LOCALHOST, port()));
+    Stream::connect_timeout(&address, Duration::from_millis(500))
+}
+
+#[cfg(not(unix))]
+fn bind() -> io::Result<Listener> {
+    Listener::bind((std::net::Ipv4Addr::LOCALHOST, port()))
+}
+
+impl InstanceListener {
+    /// Accepts handoffs for the lifetime of the process and sends
+    /// `INSTANCE:<json links>` for each, waking the UI.
+    pub fn spawn(self, tx: Sender<String>, ctx: egui::Context) {
+        std::thread::spawn(move || {
+            for stream in self.listener.incoming().flatten() {
+                let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+                let mut lines = BufReader::new(&stream).lines().map_while(Result::ok);
+                if lines.next().as_deref() != Some(HANDSHAKE) || lines.next().as_deref() != Some(self.token.as_str()) {
+                    continue;
+                }
+                let links: Vec<String> = lines.filter(|line| !line.trim().is_empty()).collect();
+                let mut writer = &stream;
+                let _ = writeln!(writer, "{}", HANDSHAKE);
+                let _ = tx.send(format!("INSTANCE:{}", serde_json::to_string(&links).unwrap_or_default()));
+                ctx.request_repaint();
+            }
+        });
+    }
+}
@@ -0,0 +1,314 @@
+use crate::i18n::{t, tf};
+use eframe::egui;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Only files that hold links are read, and only their start, so dropping a
+/// video onto the window neither blocks the UI nor fills memory.
+const LINK_FILE_EXTENSIONS: [&str; 3] = ["url", "webloc", "txt"];
+const MAX_LINK_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Links carried by a file dropped onto the window. Depending on the platform
+/// egui hands over a path, the raw bytes, or only a name.
+pub fn links_from_dropped(file: &egui::DroppedFile) -> Vec<String> {
+    if let Some(path) = &file.path {
+        if !is_link_file(path) {
+            return Vec::new();
+        }
+        let mut bytes = Vec::new();
+        return match File::open(path).and_then(|file| file.take(MAX_LINK_FILE_BYTES).read_to_end(&mut bytes)) {
+            Ok(_) => extract_links(path, &String::from_utf8_lossy(&bytes)),
+            Err(_) => Vec::new(),
+        };
+    }
+    if let Some(bytes) = &file.bytes {
+        let path = Path::new(&file.name);
+        if !is_link_file(path) {
+            return Vec::new();
+        }
+        let bytes = &bytes[..bytes.len().min(MAX_LINK_FILE_BYTES as usize)];
+        return extract_links(path, &String::from_utf8_lossy(bytes));
+    }
+    scan_urls(&file.name)
+}
+
+fn is_link_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| LINK_FILE_EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+}
+
+/// Extracts links from a dropped file: `URL=` entries of Windows `.url`
+/// shortcuts, the `<string>` of macOS `.webloc` files, or every http(s) link
+/// in plain text.
+pub fn extract_links(path: &Path, contents: &str) -> Vec<String> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if extension == "url" {
+        let links: Vec<String> = contents
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("URL="))
+            .map(|url| url.trim().to_string())
+            .filter(|url| is_http(url))
+            .collect();
+        if !links.is_empty() {
+            return links;
+        }
+    }
+
+    scan_urls(contents)
+}
+
+/// Every distinct http(s) link in `text`, stopping at whitespace, quotes or markup.
+pub fn scan_urls(text: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = find_scheme(rest) {
+        let candidate = &rest[start..];
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '\''))
+            .unwrap_or(candidate.len());
+        let link = candidate[..end].trim_end_matches(['.', ',', ';', ')']);
+        if link.len() > "https://".len() && !links.iter().any(|l| l == link) {
+            links.push(link.to_string());
+        }
+        rest = &candidate[end.max(1)..];
+    }
+    links
+}
+
+fn find_scheme(text: &str) -> Option<usize> {
+    match (text.find("http://"), text.find("https://")) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn is_http(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Query parameters that only track where a link was shared from.
+const TRACKING_PARAMS: [&str; 11] = [
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "mc_cid", "mc_eid",
+    "ref_src", "ref_url", "_hsenc", "_hsmi",
+];
+
+/// Tracking parameters whose names mean something else on other sites.
+const HOST_TRACKING_PARAMS: [(&[&str], &[&str]); 3] = [
+    (&["youtube.com", "youtu.be"], &["si", "feature"]),
+    (&["spotify.com"], &["si"]),
+    (&["instagram.com"], &["igshid", "igsh"]),
+];
+
+fn is_tracking_param(host: &str, key: &str) -> bool {
+    let host = host.split(':').next().unwrap_or(host);
+    key.starts_with("utm_")
+        || TRACKING_PARAMS.contains(&key)
+        || HOST_TRACKING_PARAMS.iter().any(|(hosts, params)| {
+            params.contains(&key) && hosts.iter().any(|site| host == *site || host.ends_with(&format!(".{}", site)))
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Video,
+    Playlist,
+}
+
+impl LinkKind {
+    /// The yt-dlp flag that makes it fetch exactly this kind.
+    pub fn yt_dlp_flag(&self) -> &'static str {
+        match self {
+            LinkKind::Video => "--no-playlist",
+            LinkKind::Playlist => "--yes-playlist",
+        }
+    }
+}
+
+/// Cleans up user input before it reaches yt-dlp: trims it, adds a missing
+/// scheme, expands YouTube short forms and drops tracking parameters.
+pub fn normalize(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(t("links.empty").to_string());
+    }
+    if input.contains(char::is_whitespace) {
+        return Err(tf("links.not_url", &[("input", &first_line(input))]));
+    }
+
+    let with_scheme = if is_http(input) {
+        input.to_string()
+    } else if input.contains("://") {
+        return Err(tf("links.unsupported_scheme", &[("input", &input)]));
+    } else if input.split('/').next().is_some_and(|host| host.contains('.')) {
+        format!("https://{}", input)
+    } else {
+        return Err(tf("links.not_url", &[("input", &input)]));
+    };
+
+    let (scheme, rest) = with_scheme.split_once("://").unwrap_or(("https", &with_scheme));
+    let (rest, fragment) = match rest.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (rest, None),
+    };
+    let (authority, path_and_query) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, ""),
+    };
+    let (path, query) = path_and_query.split_once('?').unwrap_or((path_and_query, ""));
+    if authority.is_empty() || (!authority.contains('.') && !authority.starts_with("localhost")) {
+        return Err(tf("links.not_url", &[("input", &input)]));
+    }
+
+    let mut host = authority.to_lowercase();
+    let mut path = path.to_string();
+    let mut params: Vec<(String, String)> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (pair.to_string(), String::new()),
+        })
+        .filter(|(key, _)| !is_tracking_param(&host, key))
+        .collect();
+
+    // youtu.be/<id>, youtube.com/shorts/<id> and m.youtube.com all mean watch?v=<id>
+    if host == "youtu.be" || host == "www.youtu.be" {
+        let id = path.trim_start_matches('/').to_string();
+        if !id.is_empty() {
+            host = "www.youtube.com".to_string();
+            path = "/watch".to_string();
+            params.insert(0, ("v".to_string(), id));
+        }
+    } else if host == "youtube.com" || host == "m.youtube.com" {
+        host = "www.youtube.com".to_string();
+    }
+    if host == "www.youtube.com" {
+        if let Some(id) = path.strip_prefix("/shorts/").map(|id| id.trim_end_matches('/').to_string()) {
+            path = "/watch".to_string();
+            params.insert(0, ("v".to_string(), id));
+        }
+    }
+
+    let mut url = format!("{}://{}{}", scheme.to_lowercase(), host, path);
+    if !params.is_empty() {
+        let query: Vec<String> = params
+            .iter()
+            .map(|(key, value)| if value.is_empty() { key.clone() } else { format!("{}={}", key, value) })
+            .collect();
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+    if let Some(fragment) = fragment.filter(|fragment| !fragment.is_empty()) {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    Ok(url)
+}
+
+/// Whether `url` points at a whole collection rather than a single video.
+/// A video opened from inside a playlist (`watch?v=…&list=…`) counts as a video.
+pub fn classify(url: &str) -> LinkKind {
+    let lower = url.to_lowercase();
+    let (path, query) = lower.split_once('?').unwrap_or((&lower, ""));
+    let has_param = |name: &str| {
+        query
+            .split('&')
+            .any(|pair| pair == name || pair.starts_with(&format!("{}=", name)))
+    };
+
+    let path = path.split('#').next().unwrap_or(path).trim_end_matches('/');
+
+    let playlist_paths = ["/playlist", "/sets/", "/album/", "/channel/"];
+    // Channel tabs like `youtube.com/@name/videos`, but not single videos
+    // such as `twitch.tv/videos/<id>`.
+    let channel_tabs = ["/videos", "/shorts", "/streams"];
+    if has_param("v") {
+        LinkKind::Video
+    } else if has_param("list")
+        || playlist_paths.iter().any(|segment| path.contains(segment))
+        || channel_tabs.iter().any(|tab| path.ends_with(tab))
+    {
+        LinkKind::Playlist
+    } else {
+        LinkKind::Video
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or(text).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_youtube_short_forms() {
+        assert_eq!(normalize(" youtu.be/abc?si=x&t=10 ").unwrap(), "https://www.youtube.com/watch?v=abc&t=10");
+        assert_eq!(normalize("https://youtube.com/shorts/abc/").unwrap(), "https://www.youtube.com/watch?v=abc");
+        assert_eq!(
+            normalize("m.youtube.com/watch?v=abc&feature=share&utm_source=x").unwrap(),
+            "https://www.youtube.com/watch?v=abc"
+        );
+    }
+
+    #[test]
+    fn drops_tracking_params_only_where_they_track() {
+        assert_eq!(normalize("https://example.com/page?si=1&fbclid=2&id=3").unwrap(), "https://example.com/page?si=1&id=3");
+        assert_eq!(normalize("https://open.spotify.com/track/1?si=abc").unwrap(), "https://open.spotify.com/track/1");
+        assert_eq!(normalize("https://www.instagram.com/p/x/?igsh=abc").unwrap(), "https://www.instagram.com/p/x/");
+    }
+
+    #[test]
+    fn lowercases_host_and_keeps_fragment() {
+        assert_eq!(normalize("https://Example.COM/Path#t=1").unwrap(), "https://example.com/Path#t=1");
+        assert_eq!(normalize("http://localhost:8080/v").unwrap(), "http://localhost:8080/v");
+    }
+
+    #[test]
+    fn rejects_non_links() {
+        assert!(normalize("").is_err());
+        assert!(normalize("two words").is_err());
+        assert!(normalize("ftp://example.com/file").is_err());
+        assert!(normalize("notalink").is_err());
+        assert!(normalize("https:///path").is_err());
+    }
+
+    #[test]
+    fn classifies_playlists() {
+        assert_eq!(classify("https://www.youtube.com/playlist?list=PL1"), LinkKind::Playlist);
+        assert_eq!(classify("https://www.youtube.com/@name/videos"), LinkKind::Playlist);
+        assert_eq!(classify("https://www.youtube.com/@name/streams/#top"), LinkKind::Playlist);
+        assert_eq!(classify("https://soundcloud.com/artist/sets/album"), LinkKind::Playlist);
+    }
+
+    #[test]
+    fn classifies_videos() {
+        assert_eq!(classify("https://www.youtube.com/watch?v=abc&list=PL1"), LinkKind::Video);
+        assert_eq!(classify("https://www.twitch.tv/videos/123"), LinkKind::Video);
+        assert_eq!(classify("https://vimeo.com/123"), LinkKind::Video);
+    }
+
+    #[test]
+    fn reads_only_link_files() {
+        assert!(is_link_file(Path::new("/tmp/a.URL")));
+        assert!(is_link_file(Path::new("links.txt")));
+        assert!(is_link_file(Path::new("page.webloc")));
+        assert!(!is_link_file(Path::new("movie.mkv")));
+        assert!(!is_link_file(Path::new("README")));
+    }
+
+    #[test]
+    fn extracts_links_from_shortcuts_and_text() {
+        let shortcut = "[InternetShortcut]\r\nURL=https://example.com/a\r\n";
+        assert_eq!(extract_links(Path::new("a.URL"), shortcut), ["https://example.com/a"]);
+        let text = "see (https://example.com/a), <https://example.com/b> and https://example.com/a.";
+        assert_eq!(scan_urls(text), ["https://example.com/a", "https://example.com/b"]);
+    }
+}
@@ -1,34 +1,115 @@
+mod api;
 mod app;
+mod auth;
+mod clipboard;
+mod columns;
 mod config;
+mod desktop;
+mod ffmpeg;
+mod i18n;
+mod info_cache;
+mod instance;
+mod links;
+mod network;
+mod notifications;
+mod presets;
+mod queue;
+mod schedule;
+mod scheme;
+mod shortcuts;
+mod theme;
 mod ui;
+mod ytdlp;
 
 include!(concat!(env!("OUT_DIR"), "/version.rs"));
 
 pub use app::{YtDlpApp, FormatInfo, short_codec};
+use app::UiState;
 use eframe::egui;
 
 fn main() {
+    let links = parse_args();
+    let listener = match instance::acquire(&links) {
+        Ok(instance::Instance::Primary(listener)) => Some(listener),
+        Ok(instance::Instance::Forwarded) => return,
+        // Starting anyway would mean two windows writing the same config.
+        Ok(instance::Instance::Unreachable(e)) => {
+            eprintln!("Another instance is running but did not respond: {}", e);
+            return;
+        }
+        Err(e) => {
+            eprintln!("Single-instance check failed: {}", e);
+            None
+        }
+    };
+
     let options = eframe::NativeOptions {
         initial_window_size: Some(egui::vec2(448.0, 256.0)),
         decorated: true,
+        drag_and_drop_support: true,
+        persist_window: true,
         ..Default::default()
     };
 
     if let Err(e) = eframe::run_native(
         APP_NAME,
         options,
-        Box::new(|_cc| Box::new(YtDlpApp::default())),
+        Box::new(|cc| {
+            let mut app = YtDlpApp::default();
+            if let Some(state) = cc.storage.and_then(|storage| eframe::get_value::<UiState>(storage, UI_STATE_KEY)) {
+                app.restore_ui_state(state);
+            }
+            app.set_egui_context(cc.egui_ctx.clone());
+            if let Some(listener) = listener {
+                app.listen_for_instances(listener);
+            }
+            if !links.is_empty() {
+                app.open_links(links);
+            }
+            Box::new(app)
+        }),
     ) {
         eprintln!("Error: {}", e);
     }
 }
 
+/// eframe storage key for [`UiState`]; window geometry and egui memory
+/// (collapsing header states) are persisted by eframe itself.
+const UI_STATE_KEY: &str = "ui_state";
+
+/// Handles `--config <path>` / `--config=<path>` and returns the remaining
+/// arguments as links to open, including `lova://download?...` links which
+/// [`YtDlpApp::open_links`] unpacks.
+fn parse_args() -> Vec<String> {
+    let mut links = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            if let Some(path) = args.next() {
+                config::Config::set_config_file(path.into());
+            }
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            config::Config::set_config_file(path.into());
+        } else if !arg.starts_with('-') {
+            links.push(arg);
+        }
+    }
+    links
+}
+
 impl eframe::App for YtDlpApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.handle_messages();
+        ui::apply_theme(self, ctx, frame.info().system_theme);
         ui::render_ui(self, ctx);
         if self.is_busy() {
             ctx.request_repaint();
+        } else if self.has_pending_jobs() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(30));
         }
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, UI_STATE_KEY, &self.ui_state());
+    }
 }
@@ -0,0 +1,97 @@
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpVersion {
+    #[default]
+    Any,
+    V4,
+    V6,
+}
+
+impl IpVersion {
+    pub const ALL: [IpVersion; 3] = [IpVersion::Any, IpVersion::V4, IpVersion::V6];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IpVersion::Any => t("network.ip_any"),
+            IpVersion::V4 => t("network.ip_v4"),
+            IpVersion::V6 => t("network.ip_v6"),
+        }
+    }
+}
+
+/// Network options passed to every yt-dlp invocation.
+/// Values equal to yt-dlp's own defaults are not emitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// e.g. `socks5://127.0.0.1:1080`; empty means no proxy.
+    pub proxy: String,
+    /// e.g. `500K` or `4.2M`; empty means unlimited.
+    pub limit_rate: String,
+    pub retries: u32,
+    pub fragment_retries: u32,
+    /// Seconds; 0 keeps yt-dlp's default.
+    pub socket_timeout: u32,
+    pub ip_version: IpVersion,
+    pub concurrent_fragments: u32,
+    pub check_certificates: bool,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            proxy: String::new(),
+            limit_rate: String::new(),
+            retries: 10,
+            fragment_retries: 10,
+            socket_timeout: 0,
+            ip_version: IpVersion::Any,
+            concurrent_fragments: 1,
+            check_certificates: true,
+        }
+    }
+}
+
+impl NetworkConfig {
+    pub fn args(&self) -> Vec<String> {
+        let defaults = Self::default();
+        let mut args = Vec::new();
+
+        if !self.proxy.trim().is_empty() {
+            args.push("--proxy".to_string());
+            args.push(self.proxy.trim().to_string());
+        }
+        if !self.limit_rate.trim().is_empty() {
+            args.push("--limit-rate".to_string());
+            args.push(self.limit_rate.trim().to_string());
+        }
+        if self.retries != defaults.retries {
+            args.push("--retries".to_string());
+            args.push(self.retries.to_string());
+        }
+        if self.fragment_retries != defaults.fragment_retries {
+            args.push("--fragment-retries".to_string());
+            args.push(self.fragment_retries.to_string());
+        }
+        if self.socket_timeout > 0 {
+            args.push("--socket-timeout".to_string());
+            args.push(self.socket_timeout.to_string());
+        }
+        match self.ip_version {
+            IpVersion::Any => {}
+            IpVersion::V4 => args.push("--force-ipv4".to_string()),
+            IpVersion::V6 => args.push("--force-ipv6".to_string()),
+        }
+        if self.concurrent_fragments > 1 {
+            args.push("--concurrent-fragments".to_string());
+            args.push(self.concurrent_fragments.to_string());
+        }
+        if !self.check_certificates {
+            args.push("--no-check-certificate".to_string());
+        }
+        args
+    }
+}
@@ -0,0 +1,98 @@
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationEvent {
+    Completed,
+    Failed,
+}
+
+/// Which job events raise a desktop notification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub completed: bool,
+    pub failed: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            completed: true,
+            failed: true,
+        }
+    }
+}
+
+impl NotificationConfig {
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        match event {
+            NotificationEvent::Completed => self.completed,
+            NotificationEvent::Failed => self.failed,
+        }
+    }
+}
+
+/// Shows a notification from a background thread.
+pub fn show(event: NotificationEvent, body: String, file: Option<PathBuf>, folder: PathBuf) {
+    std::thread::spawn(move || {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname(env!("APP_NAME"))
+            .summary(match event {
+                NotificationEvent::Completed => t("notifications.completed"),
+                NotificationEvent::Failed => t("notifications.failed"),
+            })
+            .body(&body);
+        show_with_actions(notification, file, folder);
+    });
+}
+
+/// Whether notifications can offer "Open file" / "Open folder" buttons.
+/// notify-rust reports chosen actions only for freedesktop notifications;
+/// Windows toasts and macOS notifications cannot, and the app's own open
+/// buttons are the way there.
+pub const SUPPORTS_ACTIONS: bool = cfg!(all(unix, not(target_os = "macos")));
+
+/// Notifications whose actions are still awaited. Each one holds a thread
+/// until it is dismissed, so beyond this many the buttons are left out.
+#[cfg(all(unix, not(target_os = "macos")))]
+const MAX_ACTION_WAITERS: usize = 3;
+
+#[cfg(all(unix, not(target_os = "macos")))]
+static ACTION_WAITERS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// freedesktop notifications report the chosen action, so they offer to open
+/// the file or its folder.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn show_with_actions(mut notification: notify_rust::Notification, file: Option<PathBuf>, folder: PathBuf) {
+    use crate::desktop::open_path;
+    use std::sync::atomic::Ordering;
+
+    if ACTION_WAITERS.fetch_add(1, Ordering::SeqCst) >= MAX_ACTION_WAITERS {
+        ACTION_WAITERS.fetch_sub(1, Ordering::SeqCst);
+        let _ = notification.show();
+        return;
+    }
+    if file.is_some() {
+        notification.action("open_file", t("notifications.open_file"));
+    }
+    notification.action("open_folder", t("notifications.open_folder"));
+    if let Ok(handle) = notification.show() {
+        handle.wait_for_action(|action| {
+            let _ = match (action, &file) {
+                ("open_file", Some(file)) => open_path(file),
+                ("open_folder", _) => open_path(&folder),
+                _ => Ok(()),
+            };
+        });
+    }
+    ACTION_WAITERS.fetch_sub(1, Ordering::SeqCst);
+}
+
+/// Windows toasts and macOS notifications only inform; see [`SUPPORTS_ACTIONS`].
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn show_with_actions(notification: notify_rust::Notification, _file: Option<PathBuf>, _folder: PathBuf) {
+    let _ = notification.show();
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// A named format choice for downloads that skip the format list
+/// (batch links, quick download, external requests).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    pub name: String,
+    /// yt-dlp `-f` selector.
+    pub format: String,
+    pub extract_audio: bool,
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self {
+            name: "Best".to_string(),
+            format: "bv*+ba/b".to_string(),
+            extract_audio: false,
+        }
+    }
+}
+
+impl Preset {
+    /// The selector to use; merged formats fall back to the best single file
+    /// when ffmpeg is not available.
+    pub fn format_spec(&self, has_ffmpeg: bool) -> String {
+        if has_ffmpeg || !self.format.contains('+') {
+            self.format.clone()
+        } else {
+            "b".to_string()
+        }
+    }
+}
+
+pub fn default_presets() -> Vec<Preset> {
+    vec![
+        Preset::default(),
+        Preset {
+            name: "1080p".to_string(),
+            format: "bv*[height<=1080]+ba/b[height<=1080]".to_string(),
+            extract_audio: false,
+        },
+        Preset {
+            name: "720p".to_string(),
+            format: "bv*[height<=720]+ba/b[height<=720]".to_string(),
+            extract_audio: false,
+        },
+        Preset {
+            name: "Audio".to_string(),
+            format: "ba/b".to_string(),
+            extract_audio: true,
+        },
+    ]
+}
@@ -0,0 +1,263 @@
+use crate::config::Config;
+use crate::i18n::{t, tf};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    /// Was running when the app last closed.
+    Interrupted,
+}
+
+impl JobState {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            JobState::Queued => "⏸",
+            JobState::Running => "⏳",
+            JobState::Completed => "✅",
+            JobState::Failed => "❌",
+            JobState::Interrupted => "⚠",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobState::Queued => t("queue.state.queued"),
+            JobState::Running => t("queue.state.running"),
+            JobState::Completed => t("queue.state.completed"),
+            JobState::Failed => t("queue.state.failed"),
+            JobState::Interrupted => t("queue.state.interrupted"),
+        }
+    }
+}
+
+/// A file a job produced, as reported by yt-dlp once it is in its final place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadedFile {
+    pub path: String,
+    pub title: String,
+}
+
+/// Progress of a running download, from yt-dlp's `--progress-template` output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    /// 0.0–1.0, when the size is known or estimated.
+    pub fraction: Option<f32>,
+    /// Bytes per second.
+    pub speed: Option<f64>,
+    /// Seconds remaining.
+    pub eta: Option<u64>,
+}
+
+impl Progress {
+    pub const PREFIX: &'static str = "LOVA_PROGRESS ";
+    pub const TEMPLATE: &'static str = "download:LOVA_PROGRESS %(progress.downloaded_bytes)s %(progress.total_bytes)s %(progress.total_bytes_estimate)s %(progress.speed)s %(progress.eta)s";
+
+    /// Parses a line printed with [`Progress::TEMPLATE`]; missing values are `NA`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let values: Vec<Option<f64>> = line
+            .trim()
+            .strip_prefix(Self::PREFIX)?
+            .split_whitespace()
+            .map(|value| value.parse().ok())
+            .collect();
+        let [downloaded, total, estimate, speed, eta] = values[..] else {
+            return None;
+        };
+        let fraction = downloaded
+            .zip(total.or(estimate))
+            .filter(|(_, total)| *total > 0.0)
+            .map(|(downloaded, total)| (downloaded / total).clamp(0.0, 1.0) as f32);
+        Some(Self {
+            fraction,
+            speed,
+            eta: eta.map(|eta| eta as u64),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub url: String,
+    pub format_spec: String,
+    #[serde(default)]
+    pub extract_audio: bool,
+    /// Held back until the schedule window opens.
+    #[serde(default)]
+    pub scheduled: bool,
+    pub state: JobState,
+    #[serde(default)]
+    pub error: String,
+    /// Continue a partially downloaded file (`--continue`).
+    #[serde(default)]
+    pub resume: bool,
+    #[serde(default)]
+    pub files: Vec<DownloadedFile>,
+    #[serde(skip)]
+    pub progress: Option<Progress>,
+}
+
+impl Job {
+    pub fn is_unfinished(&self) -> bool {
+        matches!(self.state, JobState::Interrupted | JobState::Failed)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    pub jobs: Vec<Job>,
+    next_id: u64,
+}
+
+impl JobQueue {
+    /// Restores unfinished jobs from the last session. Jobs that were running
+    /// when the app closed come back as [`JobState::Interrupted`]. An
+    /// unreadable queue file is backed up rather than overwritten by the next
+    /// save; the returned message says where it went.
+    pub fn load() -> (Self, Option<String>) {
+        let path = Config::get_queue_path();
+        let Ok(contents) = fs::read_to_string(&path) else {
+            return (Self::default(), None);
+        };
+        let (mut queue, error) = match serde_json::from_str::<Self>(&contents) {
+            Ok(queue) => (queue, None),
+            Err(e) => {
+                let backup = path.with_extension(format!(
+                    "json.bak-{}",
+                    chrono::Local::now().format("%Y%m%d-%H%M%S")
+                ));
+                let error = match fs::rename(&path, &backup) {
+                    Ok(()) => tf("queue.read_failed", &[("error", &e), ("backup", &backup.display())]),
+                    Err(rename_error) => tf("queue.backup_failed", &[("error", &e), ("backup_error", &rename_error)]),
+                };
+                (Self::default(), Some(error))
+            }
+        };
+        queue.jobs.retain(|job| job.state != JobState::Completed);
+        for job in &mut queue.jobs {
+            if job.state == JobState::Running {
+                job.state = JobState::Interrupted;
+            }
+        }
+        (queue, error)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(Config::get_queue_path(), json)?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, url: String, format_spec: String, extract_audio: bool, scheduled: bool) -> u64 {
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id: self.next_id,
+            url,
+            format_spec,
+            extract_audio,
+            scheduled,
+            state: JobState::Queued,
+            error: String::new(),
+            resume: false,
+            files: Vec::new(),
+            progress: None,
+        });
+        self.next_id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|job| job.id == id)
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.jobs.retain(|job| job.id != id || job.state == JobState::Running);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.jobs.iter().any(|job| job.state == JobState::Running)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.jobs.iter().any(|job| job.state == JobState::Queued)
+    }
+
+    /// The first queued job allowed to start; scheduled jobs wait for `window_open`.
+    pub fn next_ready(&self, window_open: bool) -> Option<u64> {
+        self.jobs
+            .iter()
+            .find(|job| job.state == JobState::Queued && (!job.scheduled || window_open))
+            .map(|job| job.id)
+    }
+
+    /// Jobs that stopped before finishing: interrupted by a restart, or failed.
+    pub fn has_unfinished(&self) -> bool {
+        self.jobs.iter().any(Job::is_unfinished)
+    }
+
+    /// Re-queues a failed or interrupted job, continuing any partial file.
+    pub fn retry(&mut self, id: u64) {
+        if let Some(job) = self.get_mut(id) {
+            if matches!(job.state, JobState::Failed | JobState::Interrupted) {
+                job.state = JobState::Queued;
+                job.error.clear();
+                job.resume = true;
+            }
+        }
+    }
+
+    pub fn resume_all(&mut self) {
+        let ids: Vec<u64> = self.jobs.iter().map(|job| job.id).collect();
+        for id in ids {
+            self.retry(id);
+        }
+    }
+
+    /// Drops interrupted and failed jobs; queued and scheduled ones stay.
+    pub fn discard_unfinished(&mut self) {
+        self.jobs.retain(|job| !job.is_unfinished());
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(|job| matches!(job.state, JobState::Queued | JobState::Running));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_complete_progress() {
+        let progress = Progress::parse("LOVA_PROGRESS 250 1000 NA 512.5 12").unwrap();
+        assert_eq!(progress, Progress { fraction: Some(0.25), speed: Some(512.5), eta: Some(12) });
+    }
+
+    #[test]
+    fn falls_back_to_estimated_size() {
+        let progress = Progress::parse("LOVA_PROGRESS 500 NA 1000.0 NA NA").unwrap();
+        assert_eq!(progress, Progress { fraction: Some(0.5), speed: None, eta: None });
+    }
+
+    #[test]
+    fn unknown_values_are_none() {
+        assert_eq!(Progress::parse("LOVA_PROGRESS NA NA NA NA NA"), Some(Progress::default()));
+        assert_eq!(Progress::parse("LOVA_PROGRESS 10 0 NA NA NA").unwrap().fraction, None);
+        assert_eq!(Progress::parse("LOVA_PROGRESS 2000 1000 NA NA NA").unwrap().fraction, Some(1.0));
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(Progress::parse("[download] 25.0% of 1.00MiB"), None);
+        assert_eq!(Progress::parse("LOVA_PROGRESS 1 2 3"), None);
+        assert_eq!(Progress::parse("LOVA_PROGRESS 1 2 3 4 5 6"), None);
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::{DateTime, Duration, Local, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// A rate limit that applies to jobs started between `start` and `end`
+/// (minutes since local midnight, wrapping past midnight). yt-dlp takes the
+/// limit on its command line, so a running job keeps the one it started with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThrottleRule {
+    pub start: u32,
+    pub end: u32,
+    pub limit_rate: String,
+}
+
+impl Default for ThrottleRule {
+    fn default() -> Self {
+        Self {
+            start: 9 * 60,
+            end: 18 * 60,
+            limit_rate: "1M".to_string(),
+        }
+    }
+}
+
+/// When scheduled jobs may run and which rate limit applies at a given time of day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScheduleConfig {
+    pub window_start: u32,
+    pub window_end: u32,
+    pub throttle_rules: Vec<ThrottleRule>,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            window_start: 60,
+            window_end: 6 * 60,
+            throttle_rules: Vec::new(),
+        }
+    }
+}
+
+impl ScheduleConfig {
+    pub fn is_open(&self, now: DateTime<Local>) -> bool {
+        in_range(self.window_start, self.window_end, minute_of_day(now))
+    }
+
+    /// `now` if the window is open, otherwise the next time it opens.
+    pub fn next_open(&self, now: DateTime<Local>) -> DateTime<Local> {
+        if self.is_open(now) {
+            return now;
+        }
+        let today = now
+            .date_naive()
+            .and_hms_opt(self.window_start / 60, self.window_start % 60, 0)
+            .and_then(|start| start.and_local_timezone(Local).earliest())
+            .unwrap_or(now);
+        if today > now {
+            today
+        } else {
+            today + Duration::days(1)
+        }
+    }
+
+    /// The rate limit of the first throttle rule covering `now`, if any.
+    pub fn limit_rate(&self, now: DateTime<Local>) -> Option<&str> {
+        let minute = minute_of_day(now);
+        self.throttle_rules
+            .iter()
+            .find(|rule| in_range(rule.start, rule.end, minute))
+            .map(|rule| rule.limit_rate.trim())
+            .filter(|rate| !rate.is_empty())
+    }
+}
+
+fn minute_of_day(now: DateTime<Local>) -> u32 {
+    now.hour() * 60 + now.minute()
+}
+
+/// Whether `minute` lies in `[start, end)`; ranges may wrap past midnight
+/// and an empty range (`start == end`) covers the whole day.
+fn in_range(start: u32, end: u32, minute: u32) -> bool {
+    match start.cmp(&end) {
+        std::cmp::Ordering::Equal => true,
+        std::cmp::Ordering::Less => minute >= start && minute < end,
+        std::cmp::Ordering::Greater => minute >= start || minute < end,
+    }
+}
+
+pub fn format_minutes(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60 % 24, minutes % 60)
+}
+
+pub fn parse_minutes(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 1, 15, hour, minute, 0).unwrap()
+    }
+
+    fn window(start: u32, end: u32) -> ScheduleConfig {
+        ScheduleConfig {
+            window_start: start,
+            window_end: end,
+            throttle_rules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn range_boundaries() {
+        assert!(in_range(60, 360, 60));
+        assert!(in_range(60, 360, 359));
+        assert!(!in_range(60, 360, 360));
+        assert!(!in_range(60, 360, 59));
+    }
+
+    #[test]
+    fn range_wrapping_past_midnight() {
+        let (start, end) = (23 * 60, 6 * 60);
+        assert!(in_range(start, end, 23 * 60));
+        assert!(in_range(start, end, 0));
+        assert!(in_range(start, end, 6 * 60 - 1));
+        assert!(!in_range(start, end, 6 * 60));
+        assert!(!in_range(start, end, 23 * 60 - 1));
+        assert!(!in_range(start, end, 12 * 60));
+    }
+
+    #[test]
+    fn equal_start_and_end_covers_the_day() {
+        for minute in [0, 600, 1439] {
+            assert!(in_range(600, 600, minute));
+        }
+        assert_eq!(window(600, 600).next_open(at(3, 0)), at(3, 0));
+    }
+
+    #[test]
+    fn next_open_in_wrapping_window() {
+        let schedule = window(23 * 60, 6 * 60);
+        assert_eq!(schedule.next_open(at(23, 30)), at(23, 30));
+        assert_eq!(schedule.next_open(at(5, 59)), at(5, 59));
+        assert_eq!(schedule.next_open(at(6, 0)), at(23, 0));
+        assert_eq!(schedule.next_open(at(12, 0)), at(23, 0));
+    }
+
+    #[test]
+    fn next_open_rolls_over_to_tomorrow() {
+        let schedule = window(60, 6 * 60);
+        assert_eq!(schedule.next_open(at(0, 59)), at(1, 0));
+        assert_eq!(schedule.next_open(at(1, 0)), at(1, 0));
+        assert_eq!(schedule.next_open(at(6, 0)), at(1, 0) + Duration::days(1));
+    }
+
+    #[test]
+    fn first_matching_rule_limits_the_rate() {
+        let schedule = ScheduleConfig {
+            throttle_rules: vec![
+                ThrottleRule { start: 22 * 60, end: 2 * 60, limit_rate: " ".to_string() },
+                ThrottleRule { start: 9 * 60, end: 18 * 60, limit_rate: " 1M ".to_string() },
+                ThrottleRule { start: 0, end: 0, limit_rate: "5M".to_string() },
+            ],
+            ..window(0, 0)
+        };
+        assert_eq!(schedule.limit_rate(at(9, 0)), Some("1M"));
+        assert_eq!(schedule.limit_rate(at(18, 0)), Some("5M"));
+        assert_eq!(schedule.limit_rate(at(23, 0)), None);
+    }
+
+    #[test]
+    fn formats_and_parses_times() {
+        assert_eq!(format_minutes(23 * 60 + 5), "23:05");
+        assert_eq!(parse_minutes(" 06:30 "), Some(390));
+        assert_eq!(parse_minutes("24:00"), None);
+        assert_eq!(parse_minutes("6"), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::io;
+use std::process::Command;
+
+/// `lova://download?url=…&preset=…`, as produced by bookmarklets.
+pub const SCHEME: &str = env!("APP_NAME_LOWER");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemeLink {
+    pub url: String,
+    pub preset: Option<String>,
+}
+
+/// Parses a `lova://download?...` argument; `None` for anything else.
+pub fn parse(arg: &str) -> Option<SchemeLink> {
+    let rest = arg.trim().strip_prefix(SCHEME)?.strip_prefix("://")?;
+    let (action, query) = rest.split_once('?')?;
+    if action.trim_end_matches('/') != "download" {
+        return None;
+    }
+
+    let mut link = SchemeLink { url: String::new(), preset: None };
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode(value);
+        match key {
+            "url" => link.url = value,
+            "preset" if !value.is_empty() => link.preset = Some(value),
+            _ => {}
+        }
+    }
+    (!link.url.is_empty()).then_some(link)
+}
+
+/// Decodes `%XX` escapes and `+` as space; invalid escapes are kept as is.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match value
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Makes this executable the handler for `lova://` links of the current user.
+#[cfg(target_os = "windows")]
+pub fn register() -> io::Result<()> {
+    let exe = std::env::current_exe()?;
+    let key = format!(r"HKCU\Software\Classes\{}", SCHEME);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let entries = [
+        (key.clone(), None, format!("URL:{} Protocol", env!("APP_NAME"))),
+        (key.clone(), Some("URL Protocol"), String::new()),
+        (format!(r"{}\shell\open\command", key), None, command),
+    ];
+    for (key, name, data) in entries {
+        let mut reg = Command::new("reg");
+        reg.args(["add", &key, "/f", "/t", "REG_SZ", "/d", &data]);
+        match name {
+            Some(name) => reg.args(["/v", name]),
+            None => reg.arg("/ve"),
+        };
+        check(reg.status()?, "reg add")?;
+    }
+    Ok(())
+}
+
+/// Installs a desktop entry for `x-scheme-handler/lova` and makes it the default.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn register() -> io::Result<()> {
+    let exe = std::env::current_exe()?;
+    let applications = dirs::data_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?
+        .join("applications");
+    std::fs::create_dir_all(&applications)?;
+
+    let file_name = format!("{}-handler.desktop", SCHEME);
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={}\n\
+         Exec=\"{}\" %u\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/{};\n",
+        env!("APP_NAME"),
+        exe.display().to_string().replace('\\', "\\\\").replace('"', "\\\""),
+        SCHEME
+    );
+    std::fs::write(applications.join(&file_name), entry)?;
+
+    let status = Command::new("xdg-mime")
+        .args(["default", &file_name, &format!("x-scheme-handler/{}", SCHEME)])
+        .status()?;
+    check(status, "xdg-mime")
+}
+
+/// macOS reads URL schemes from the app bundle's Info.plist only.
+#[cfg(target_os = "macos")]
+pub fn register() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "declare the scheme in the app bundle's Info.plist",
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn check(status: std::process::ExitStatus, program: &str) -> io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} exited with {}", program, status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(query: &str) -> Option<SchemeLink> {
+        parse(&format!("{}://download?{}", SCHEME, query))
+    }
+
+    #[test]
+    fn parses_url_and_preset() {
+        assert_eq!(
+            link("url=https%3A%2F%2Fexample.com%2Fwatch%3Fv%3D1%26t%3D2&preset=Audio+only"),
+            Some(SchemeLink {
+                url: "https://example.com/watch?v=1&t=2".to_string(),
+                preset: Some("Audio only".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_other_actions_and_schemes() {
+        assert_eq!(parse(&format!("{}://open?url=https%3A%2F%2Fexample.com", SCHEME)), None);
+        assert_eq!(parse("https://download?url=x"), None);
+        assert_eq!(link("preset=Best"), None);
+        assert_eq!(link("url="), None);
+    }
+
+    #[test]
+    fn empty_preset_is_none() {
+        assert_eq!(link("url=https://example.com&preset=").and_then(|link| link.preset), None);
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(percent_decode("a%20b+c"), "a b c");
+        assert_eq!(percent_decode("%2B%2b"), "++");
+        assert_eq!(percent_decode("%C3%A4"), "ä");
+    }
+
+    #[test]
+    fn keeps_invalid_escapes() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%4"), "%4");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        assert_eq!(percent_decode("%+1"), "% 1");
+        assert_eq!(percent_decode("%%41"), "%A");
+    }
+
+    #[test]
+    fn escape_before_multibyte_character() {
+        assert_eq!(percent_decode("%ä"), "%ä");
+        assert_eq!(percent_decode("%4ä"), "%4ä");
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced() {
+        assert_eq!(percent_decode("%FF"), "\u{FFFD}");
+    }
+}
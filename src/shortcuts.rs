@@ -0,0 +1,317 @@
+use crate::i18n::t;
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+/// Everything that can be bound to a shortcut or run from the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    PasteAndFetch,
+    FetchFormats,
+    Download,
+    QuickDownload,
+    Schedule,
+    Cancel,
+    NextFormat,
+    PreviousFormat,
+    ToggleSettings,
+    CommandPalette,
+    Clear,
+    UpdateYtDlp,
+    ClearFinished,
+}
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::PasteAndFetch,
+        Action::FetchFormats,
+        Action::Download,
+        Action::QuickDownload,
+        Action::Schedule,
+        Action::Cancel,
+        Action::NextFormat,
+        Action::PreviousFormat,
+        Action::ToggleSettings,
+        Action::CommandPalette,
+        Action::Clear,
+        Action::UpdateYtDlp,
+        Action::ClearFinished,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::PasteAndFetch => t("action.paste_and_fetch"),
+            Action::FetchFormats => t("action.fetch_formats"),
+            Action::Download => t("action.download"),
+            Action::QuickDownload => t("action.quick_download"),
+            Action::Schedule => t("action.schedule"),
+            Action::Cancel => t("action.cancel"),
+            Action::NextFormat => t("action.next_format"),
+            Action::PreviousFormat => t("action.previous_format"),
+            Action::ToggleSettings => t("action.toggle_settings"),
+            Action::CommandPalette => t("action.command_palette"),
+            Action::Clear => t("action.clear"),
+            Action::UpdateYtDlp => t("action.update_yt_dlp"),
+            Action::ClearFinished => t("queue.clear_finished"),
+        }
+    }
+
+    fn default_keys(&self) -> &'static str {
+        match self {
+            Action::PasteAndFetch => "Ctrl+V",
+            Action::FetchFormats => "F5",
+            Action::Download => "Ctrl+Enter",
+            Action::QuickDownload => "Ctrl+Shift+Enter",
+            Action::Cancel => "Escape",
+            Action::NextFormat => "Down",
+            Action::PreviousFormat => "Up",
+            Action::ToggleSettings => "Ctrl+Shift+S",
+            Action::CommandPalette => "Ctrl+K",
+            Action::Schedule | Action::Clear | Action::UpdateYtDlp | Action::ClearFinished => "",
+        }
+    }
+}
+
+/// Keys bound to an action, written like `Ctrl+Shift+K`; empty leaves it unbound.
+/// An entry without an action counts as a duplicate and is dropped by
+/// [`complete_bindings`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Binding {
+    pub action: Action,
+    pub keys: String,
+}
+
+impl Default for Binding {
+    fn default() -> Self {
+        Self {
+            action: Action::ALL[0],
+            keys: String::new(),
+        }
+    }
+}
+
+pub fn default_bindings() -> Vec<Binding> {
+    Action::ALL
+        .iter()
+        .map(|action| Binding {
+            action: *action,
+            keys: action.default_keys().to_string(),
+        })
+        .collect()
+}
+
+/// Adds bindings for actions missing from an older config and drops duplicates.
+pub fn complete_bindings(bindings: &mut Vec<Binding>) {
+    let mut seen = Vec::new();
+    bindings.retain(|binding| {
+        let first = !seen.contains(&binding.action);
+        seen.push(binding.action);
+        first
+    });
+    for default in default_bindings() {
+        if !seen.contains(&default.action) {
+            bindings.push(default);
+        }
+    }
+}
+
+const KEY_NAMES: [(&str, Key); 65] = [
+    ("Up", Key::ArrowUp),
+    ("Down", Key::ArrowDown),
+    ("Left", Key::ArrowLeft),
+    ("Right", Key::ArrowRight),
+    ("Escape", Key::Escape),
+    ("Tab", Key::Tab),
+    ("Backspace", Key::Backspace),
+    ("Enter", Key::Enter),
+    ("Space", Key::Space),
+    ("Insert", Key::Insert),
+    ("Delete", Key::Delete),
+    ("Home", Key::Home),
+    ("End", Key::End),
+    ("PageUp", Key::PageUp),
+    ("PageDown", Key::PageDown),
+    ("Minus", Key::Minus),
+    ("Plus", Key::PlusEquals),
+    ("0", Key::Num0),
+    ("1", Key::Num1),
+    ("2", Key::Num2),
+    ("3", Key::Num3),
+    ("4", Key::Num4),
+    ("5", Key::Num5),
+    ("6", Key::Num6),
+    ("7", Key::Num7),
+    ("8", Key::Num8),
+    ("9", Key::Num9),
+    ("A", Key::A),
+    ("B", Key::B),
+    ("C", Key::C),
+    ("D", Key::D),
+    ("E", Key::E),
+    ("F", Key::F),
+    ("G", Key::G),
+    ("H", Key::H),
+    ("I", Key::I),
+    ("J", Key::J),
+    ("K", Key::K),
+    ("L", Key::L),
+    ("M", Key::M),
+    ("N", Key::N),
+    ("O", Key::O),
+    ("P", Key::P),
+    ("Q", Key::Q),
+    ("R", Key::R),
+    ("S", Key::S),
+    ("T", Key::T),
+    ("U", Key::U),
+    ("V", Key::V),
+    ("W", Key::W),
+    ("X", Key::X),
+    ("Y", Key::Y),
+    ("Z", Key::Z),
+    ("F1", Key::F1),
+    ("F2", Key::F2),
+    ("F3", Key::F3),
+    ("F4", Key::F4),
+    ("F5", Key::F5),
+    ("F6", Key::F6),
+    ("F7", Key::F7),
+    ("F8", Key::F8),
+    ("F9", Key::F9),
+    ("F10", Key::F10),
+    ("F11", Key::F11),
+    ("F12", Key::F12),
+];
+
+/// What a binding listens for. Keys that egui does not report on their own
+/// (like `,`) are matched against the typed text instead. egui types no text
+/// while Ctrl/Cmd is held on Windows and macOS, so those keys only combine
+/// with Shift and Alt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    Key(KeyboardShortcut),
+    Text(Modifiers, char),
+}
+
+impl Trigger {
+    pub fn parse(keys: &str) -> Result<Option<Self>, String> {
+        let keys = keys.trim();
+        if keys.is_empty() {
+            return Ok(None);
+        }
+
+        let mut modifiers = Modifiers::NONE;
+        let mut parts: Vec<&str> = keys.split('+').map(str::trim).collect();
+        // "Ctrl++" ends with an empty part for the plus key itself.
+        let key = match parts.pop() {
+            Some("") if parts.last() == Some(&"") => {
+                parts.pop();
+                "Plus"
+            }
+            Some(key) => key,
+            None => "",
+        };
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => modifiers = modifiers | Modifiers::COMMAND,
+                "shift" => modifiers = modifiers | Modifiers::SHIFT,
+                "alt" | "option" => modifiers = modifiers | Modifiers::ALT,
+                _ => return Err(format!("{}: {}", t("shortcuts.unknown_modifier"), part)),
+            }
+        }
+
+        let named = if key.eq_ignore_ascii_case("esc") { "Escape" } else { key };
+        if let Some((_, key)) = KEY_NAMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(named)) {
+            return Ok(Some(Trigger::Key(KeyboardShortcut::new(modifiers, *key))));
+        }
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(_), None) if modifiers.command => Err(format!("{}: {}", t("shortcuts.needs_named_key"), key)),
+            (Some(c), None) if !c.is_whitespace() => Ok(Some(Trigger::Text(modifiers, c))),
+            _ => Err(format!("{}: {}", t("shortcuts.unknown_key"), key)),
+        }
+    }
+
+    /// Plain keys would steal typing from text fields, so they only fire
+    /// while no field has keyboard focus.
+    pub fn needs_free_keyboard(&self) -> bool {
+        let modifiers = match self {
+            Trigger::Key(shortcut) => shortcut.modifiers,
+            Trigger::Text(modifiers, _) => *modifiers,
+        };
+        !modifiers.command && !modifiers.alt
+    }
+
+    /// Checks this frame's input and consumes the matching event.
+    pub fn consume(&self, input: &mut egui::InputState) -> bool {
+        match self {
+            Trigger::Key(shortcut) => input.consume_shortcut(shortcut),
+            Trigger::Text(modifiers, c) => {
+                let text = c.to_string();
+                let pressed = input.modifiers.matches(*modifiers);
+                let before = input.events.len();
+                input.events.retain(|event| !(pressed && matches!(event, egui::Event::Text(typed) if *typed == text)));
+                input.events.len() != before
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(modifiers: Modifiers, key: Key) -> Option<Trigger> {
+        Some(Trigger::Key(KeyboardShortcut::new(modifiers, key)))
+    }
+
+    #[test]
+    fn parses_named_keys_and_modifiers() {
+        assert_eq!(Trigger::parse("Ctrl+Shift+K"), Ok(key(Modifiers::COMMAND | Modifiers::SHIFT, Key::K)));
+        assert_eq!(Trigger::parse(" cmd + enter "), Ok(key(Modifiers::COMMAND, Key::Enter)));
+        assert_eq!(Trigger::parse("Esc"), Ok(key(Modifiers::NONE, Key::Escape)));
+        assert_eq!(Trigger::parse("f5"), Ok(key(Modifiers::NONE, Key::F5)));
+        assert_eq!(Trigger::parse(""), Ok(None));
+    }
+
+    #[test]
+    fn parses_plus_key() {
+        assert_eq!(Trigger::parse("Ctrl++"), Ok(key(Modifiers::COMMAND, Key::PlusEquals)));
+        assert_eq!(Trigger::parse("Ctrl+Plus"), Ok(key(Modifiers::COMMAND, Key::PlusEquals)));
+    }
+
+    #[test]
+    fn parses_typed_characters() {
+        assert_eq!(Trigger::parse("?"), Ok(Some(Trigger::Text(Modifiers::NONE, '?'))));
+        assert_eq!(Trigger::parse("Alt+,"), Ok(Some(Trigger::Text(Modifiers::ALT, ','))));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!(Trigger::parse("Ctrl+NoSuchKey").is_err());
+        assert!(Trigger::parse("Hyper+K").is_err());
+        assert!(Trigger::parse("Ctrl+").is_err());
+        assert!(Trigger::parse("Ctrl+,").is_err());
+    }
+
+    #[test]
+    fn default_bindings_parse() {
+        for binding in default_bindings() {
+            assert!(Trigger::parse(&binding.keys).is_ok(), "{:?}", binding);
+        }
+    }
+
+    #[test]
+    fn completes_missing_and_drops_duplicate_bindings() {
+        let mut bindings = vec![
+            Binding { action: Action::Cancel, keys: "Q".to_string() },
+            Binding { action: Action::Cancel, keys: "W".to_string() },
+        ];
+        complete_bindings(&mut bindings);
+        assert_eq!(bindings.len(), Action::ALL.len());
+        assert_eq!(bindings[0], Binding { action: Action::Cancel, keys: "Q".to_string() });
+        for action in Action::ALL {
+            assert_eq!(bindings.iter().filter(|binding| binding.action == action).count(), 1);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::i18n::t;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::System, ThemeMode::Light, ThemeMode::Dark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemeMode::System => t("theme.system"),
+            ThemeMode::Light => t("theme.light"),
+            ThemeMode::Dark => t("theme.dark"),
+        }
+    }
+}
+
+/// Everything that goes into the applied style, so it is only rebuilt on change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeSettings {
+    pub mode: ThemeMode,
+    pub accent: [u8; 3],
+    pub font_scale: f32,
+    pub high_contrast: bool,
+    pub system_theme: Option<eframe::Theme>,
+}
+
+impl ThemeSettings {
+    pub fn is_dark(&self) -> bool {
+        match self.mode {
+            ThemeMode::System => self.system_theme != Some(eframe::Theme::Light),
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut visuals = if self.is_dark() {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        let [r, g, b] = self.accent;
+        let accent = egui::Color32::from_rgb(r, g, b);
+        visuals.selection.bg_fill = accent;
+        visuals.hyperlink_color = accent;
+        if self.high_contrast {
+            make_high_contrast(&mut visuals);
+        }
+
+        // Scale from egui's defaults so repeated changes do not compound.
+        let mut style = egui::Style {
+            visuals,
+            ..(*ctx.style()).clone()
+        };
+        style.text_styles = egui::Style::default().text_styles;
+        for font in style.text_styles.values_mut() {
+            font.size *= self.font_scale;
+        }
+        ctx.set_style(style);
+    }
+}
+
+/// Pure black and white with visible outlines on every widget, so nothing
+/// depends on subtle shades to be told apart.
+fn make_high_contrast(visuals: &mut egui::Visuals) {
+    let (fg, bg) = if visuals.dark_mode {
+        (egui::Color32::WHITE, egui::Color32::BLACK)
+    } else {
+        (egui::Color32::BLACK, egui::Color32::WHITE)
+    };
+    visuals.override_text_color = Some(fg);
+    visuals.panel_fill = bg;
+    visuals.window_fill = bg;
+    visuals.extreme_bg_color = bg;
+    visuals.faint_bg_color = bg;
+    visuals.window_stroke = egui::Stroke::new(1.0, fg);
+    visuals.selection.stroke = egui::Stroke::new(2.0, fg);
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.bg_stroke = egui::Stroke::new(1.0, fg);
+        widget.fg_stroke = egui::Stroke::new(1.5, fg);
+    }
+    visuals.widgets.hovered.bg_stroke.width = 2.0;
+    visuals.widgets.active.bg_stroke.width = 2.0;
+}
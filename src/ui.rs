@@ -1,5 +1,21 @@
 use eframe::egui;
-use crate::{YtDlpApp, short_codec};
+use std::path::PathBuf;
+use crate::app::StatusKind;
+use crate::YtDlpApp;
+use egui_extras::{Column, TableBuilder};
+use crate::network::IpVersion;
+use crate::theme::{ThemeMode, ThemeSettings};
+use crate::queue::{DownloadedFile, JobState};
+use crate::schedule::{format_minutes, parse_minutes, ThrottleRule};
+use crate::clipboard::default_patterns;
+use crate::columns::{default_columns, FormatColumn};
+use crate::config::Config;
+use crate::links::links_from_dropped;
+use crate::notifications::{self, NotificationEvent};
+use crate::ytdlp::UpdateChannel;
+use crate::presets::Preset;
+use crate::i18n::{self, t, tf, tn, Language};
+use crate::shortcuts::{default_bindings, Action, Trigger};
 
 fn text_edit_style(row_height: f32, margin: f32, ui: &mut egui::Ui, text: &mut String, hint: Option<&str>, width: f32) -> egui::Response {
     ui.add_sized(
@@ -11,34 +27,127 @@ fn text_edit_style(row_height: f32, margin: f32, ui: &mut egui::Ui, text: &mut S
     ).on_hover_text(hint.unwrap_or(""))
 }
 
+/// Emoji buttons with hover text, or text buttons when the user turned icon
+/// labels off. Screen readers get the text label either way.
+#[derive(Clone, Copy)]
+struct IconStyle {
+    size: f32,
+    spacing: f32,
+    text_labels: bool,
+}
+
+impl IconStyle {
+    fn new(config: &Config) -> Self {
+        Self {
+            size: config.icon_button_size,
+            spacing: config.spacing,
+            text_labels: config.text_labels,
+        }
+    }
+
+    fn button(&self, ui: &mut egui::Ui, icon: &str, label: &str) -> egui::Response {
+        self.toggle_button(ui, icon, label, false)
+    }
+
+    /// A button that shows an on/off state through its fill and its accessible state.
+    fn toggle_button(&self, ui: &mut egui::Ui, icon: &str, label: &str, selected: bool) -> egui::Response {
+        let fill = if selected {
+            ui.visuals().selection.bg_fill
+        } else {
+            ui.visuals().widgets.inactive.bg_fill
+        };
+        let response = if self.text_labels {
+            ui.add(egui::Button::new(label).fill(fill).min_size(egui::vec2(0.0, self.size)))
+        } else {
+            ui.add_sized(egui::Vec2::splat(self.size), egui::Button::new(icon).fill(fill))
+                .on_hover_text(label)
+        };
+        response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Button, selected, label));
+        response
+    }
+
+    fn small_button(&self, ui: &mut egui::Ui, icon: &str, label: &str) -> egui::Response {
+        let response = if self.text_labels {
+            ui.small_button(label)
+        } else {
+            ui.small_button(icon).on_hover_text(label)
+        };
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, label));
+        response
+    }
+
+    /// Width of a row of buttons with these labels, including spacing.
+    fn width(&self, ui: &egui::Ui, labels: &[&str]) -> f32 {
+        labels
+            .iter()
+            .map(|label| {
+                let width = if self.text_labels {
+                    let font = egui::TextStyle::Button.resolve(ui.style());
+                    let text = ui.fonts(|fonts| fonts.layout_no_wrap(label.to_string(), font, egui::Color32::WHITE).size().x);
+                    text + ui.spacing().button_padding.x * 2.0
+                } else {
+                    self.size
+                };
+                width + self.spacing
+            })
+            .sum()
+    }
+
+    /// Drops the leading emoji of a label when icons are turned off.
+    fn plain<'a>(&self, text: &'a str) -> &'a str {
+        if self.text_labels {
+            text.trim_start_matches(|c: char| !c.is_alphanumeric())
+        } else {
+            text
+        }
+    }
+}
+
 fn render_top_bar(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
     ui.horizontal(|ui| {
         ui.heading(":D");
+
+        let mut preset = app.selected_preset().name;
+        egui::ComboBox::from_id_source("preset")
+            .selected_text(&preset)
+            .show_ui(ui, |ui| {
+                for option in &app.config.presets {
+                    ui.selectable_value(&mut preset, option.name.clone(), &option.name)
+                        .on_hover_text(&option.format);
+                }
+            })
+            .response
+            .on_hover_text(t("top.preset_hint"));
+        app.preset = preset;
+
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-            let settings_button = egui::Button::new("⚙")
-                .fill(if app.show_settings {
-                    ui.style().visuals.selection.bg_fill
-                } else {
-                    ui.style().visuals.widgets.inactive.bg_fill
-                });
-            
-            if ui.add_sized(
-                egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-                settings_button
-            ).clicked() {
+            if icons.toggle_button(ui, "⚙", t("settings.title"), app.show_settings).clicked() {
                 app.show_settings = !app.show_settings;
             }
+
+            if !app.config.profiles.is_empty() {
+                let mut selected = app.config.active_profile.clone();
+                egui::ComboBox::from_id_source("settings_profile")
+                    .selected_text(if selected.is_empty() { t("top.profile") } else { selected.as_str() })
+                    .show_ui(ui, |ui| {
+                        for profile in &app.config.profiles {
+                            ui.selectable_value(&mut selected, profile.name.clone(), &profile.name);
+                        }
+                    });
+                if selected != app.config.active_profile {
+                    app.apply_profile(&selected);
+                }
+            }
         });
     });
 }
 
 fn render_url_input(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
     ui.horizontal(|ui| {
         // Paste button
-        if ui.add_sized(
-            egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-            egui::Button::new("📋")
-        ).on_hover_text("Paste").clicked() {
+        if icons.button(ui, "📋", t("url.paste")).clicked() {
             if let Ok(mut clipboard) = arboard::Clipboard::new() {
                 if let Ok(text) = clipboard.get_text() {
                     app.url = text;
@@ -53,7 +162,12 @@ fn render_url_input(app: &mut YtDlpApp, ui: &mut egui::Ui) {
         let available = if app.url.is_empty() {
             ui.available_width()
         } else {
-            ui.available_width() - (app.config.icon_button_size * 2.0 + app.config.spacing * 2.0)
+            let download_label = tf("action.download_preset", &[("preset", &app.selected_preset().name)]);
+            let mut labels = vec![t("action.fetch_formats"), &download_label, t("action.clear")];
+            if app.formats_cached {
+                labels.push(t("action.refresh_formats"));
+            }
+            ui.available_width() - icons.width(ui, &labels)
         };
         
         let response = text_edit_style(
@@ -61,7 +175,7 @@ fn render_url_input(app: &mut YtDlpApp, ui: &mut egui::Ui) {
             app.config.margin,
             ui,
             &mut app.url,
-            Some("Paste URL here"),
+            Some(t("url.hint")),
             available
         );
         
@@ -77,21 +191,988 @@ fn render_url_input(app: &mut YtDlpApp, ui: &mut egui::Ui) {
 }
 
 fn render_url_action_buttons(app: &mut YtDlpApp, ui: &mut egui::Ui) {
-    if ui.add_sized(
-        egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-        egui::Button::new("🔍")
-    ).on_hover_text("Fetch Formats").clicked() {
+    let icons = IconStyle::new(&app.config);
+    if icons.button(ui, "🔍", t("action.fetch_formats")).clicked() {
         app.fetch_formats();
     }
-    if ui.add_sized(
-        egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-        egui::Button::new("❌")
-    ).on_hover_text("Clear").clicked() {
+    if app.formats_cached && icons.button(ui, "🔄", t("action.refresh_formats")).clicked() {
+        app.refresh_formats();
+    }
+    if icons.button(ui, "⚡", &tf("action.download_preset", &[("preset", &app.selected_preset().name)])).clicked() {
+        app.download_with_preset();
+    }
+    if icons.button(ui, "❌", t("action.clear")).clicked() {
         app.clear_state();
     }
 }
 
+fn render_yt_dlp_status(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        match &app.yt_dlp_version {
+            Some(version) => {
+                let label = ui.label(format!("✅ yt-dlp {}", version));
+                match &app.yt_dlp_resolved {
+                    Some(path) => label.on_hover_text(path),
+                    None => label,
+                }
+            }
+            None => ui.colored_label(ui.visuals().error_fg_color, t("ytdlp.not_found")),
+        };
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.add_enabled_ui(app.yt_dlp_version.is_some() && !app.is_updating(), |ui| {
+                if ui.button(t("ytdlp.update")).on_hover_text("yt-dlp -U").clicked() {
+                    app.update_yt_dlp(None);
+                }
+
+                let mut channel = app.config.yt_dlp_channel;
+                egui::ComboBox::from_id_source("yt_dlp_channel")
+                    .selected_text(channel.as_str())
+                    .show_ui(ui, |ui| {
+                        for option in UpdateChannel::ALL {
+                            ui.selectable_value(&mut channel, option, option.as_str());
+                        }
+                    });
+                if channel != app.config.yt_dlp_channel {
+                    app.config.yt_dlp_channel = channel;
+                    app.save_config();
+                    app.update_yt_dlp(Some(channel));
+                }
+            });
+        });
+    });
+
+    if !app.yt_dlp_output.is_empty() {
+        egui::ScrollArea::vertical()
+            .id_source("yt_dlp_output")
+            .max_height(app.config.row_height * 3.0)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new(&app.yt_dlp_output).monospace().small());
+            });
+    }
+}
+
+fn render_download_options(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let has_ffmpeg = app.ffmpeg_location.is_some();
+    let video_only = app.selected_format
+        .and_then(|index| app.formats.get(index))
+        .is_some_and(|format| format.is_video_only());
+
+    ui.horizontal(|ui| {
+        if video_only {
+            ui.add_enabled(has_ffmpeg, egui::Checkbox::new(&mut app.merge_audio, t("options.merge_audio")))
+                .on_disabled_hover_text(t("options.merge_requires_ffmpeg"));
+        }
+        ui.add_enabled(has_ffmpeg, egui::Checkbox::new(&mut app.extract_audio, t("options.extract_audio")))
+            .on_disabled_hover_text(t("options.extract_requires_ffmpeg"));
+    });
+
+    if app.auth_profile().is_some_and(|profile| profile.twofactor) {
+        ui.horizontal(|ui| {
+            ui.label(t("options.twofactor"));
+            let width = ui.available_width();
+            text_edit_style(
+                app.config.row_height,
+                app.config.margin,
+                ui,
+                &mut app.twofactor_code,
+                Some(t("options.twofactor_hint")),
+                width
+            );
+        });
+    }
+}
+
+fn time_of_day(value: &mut u32) -> egui::DragValue<'_> {
+    egui::DragValue::new(value)
+        .clamp_range(0..=(24 * 60 - 1))
+        .speed(5.0)
+        .custom_formatter(|minutes, _| format_minutes(minutes as u32))
+        .custom_parser(|text| parse_minutes(text).map(f64::from))
+}
+
+fn render_schedule_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("schedule.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let schedule = &mut app.config.schedule;
+
+            ui.horizontal(|ui| {
+                ui.label(t("schedule.window_from"));
+                ui.add(time_of_day(&mut schedule.window_start));
+                ui.label(t("schedule.window_to"));
+                ui.add(time_of_day(&mut schedule.window_end));
+            });
+
+            ui.label(t("schedule.rate_limits"));
+            let mut remove = None;
+            for (index, rule) in schedule.throttle_rules.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(time_of_day(&mut rule.start));
+                        ui.label("–");
+                        ui.add(time_of_day(&mut rule.end));
+                        let available = ui.available_width() - icons.width(ui, &[t("schedule.remove_rule")]);
+                        text_edit_style(
+                            app.config.row_height,
+                            app.config.margin,
+                            ui,
+                            &mut rule.limit_rate,
+                            Some(t("schedule.rate_hint")),
+                            available
+                        );
+                        if icons.button(ui, "❌", t("schedule.remove_rule")).clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                });
+            }
+            if let Some(index) = remove {
+                schedule.throttle_rules.remove(index);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t("schedule.add_rule")).clicked() {
+                    app.config.schedule.throttle_rules.push(ThrottleRule::default());
+                }
+                if ui.button(t("schedule.save")).clicked() {
+                    app.save_config();
+                }
+            });
+        });
+}
+
+fn render_clipboard_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("clipboard.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut enabled = app.config.clipboard.enabled;
+            if ui.checkbox(&mut enabled, t("clipboard.watch")).changed() {
+                app.set_clipboard_watch(enabled);
+                app.save_config();
+            }
+            ui.checkbox(&mut app.config.clipboard.auto_fetch, t("clipboard.auto_fetch"))
+                .on_hover_text(t("clipboard.auto_fetch_hint"));
+
+            ui.label(t("clipboard.patterns"));
+            let mut patterns = app.config.clipboard.patterns.join("\n");
+            if ui.add(
+                egui::TextEdit::multiline(&mut patterns)
+                    .code_editor()
+                    .desired_rows(4)
+                    .desired_width(f32::INFINITY)
+            ).changed() {
+                app.config.clipboard.patterns = patterns.lines().map(str::to_string).collect();
+            }
+            if !app.clipboard_error.is_empty() {
+                ui.colored_label(ui.visuals().error_fg_color, &app.clipboard_error);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t("clipboard.reset_patterns")).clicked() {
+                    app.config.clipboard.patterns = default_patterns();
+                }
+                if ui.button(t("clipboard.save")).clicked() {
+                    app.save_config();
+                }
+            });
+        });
+}
+
+fn render_notification_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("notifications.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let notifications = &mut app.config.notifications;
+            ui.checkbox(&mut notifications.completed, t("notifications.on_completed"));
+            ui.checkbox(&mut notifications.failed, t("notifications.on_failed"));
+            if !notifications::SUPPORTS_ACTIONS {
+                ui.label(egui::RichText::new(t("notifications.no_actions")).small().weak());
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t("notifications.test")).clicked() {
+                    notifications::show(
+                        NotificationEvent::Completed,
+                        t("notifications.test_body").to_string(),
+                        None,
+                        PathBuf::from(&app.download_dir),
+                    );
+                }
+                if ui.button(t("notifications.save")).clicked() {
+                    app.save_config();
+                }
+            });
+        });
+}
+
+fn render_post_download_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("hook.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(t("hook.description"));
+            let width = ui.available_width();
+            text_edit_style(
+                app.config.row_height,
+                app.config.margin,
+                ui,
+                &mut app.config.post_download_command,
+                Some(t("hook.hint")),
+                width
+            );
+            ui.label(egui::RichText::new(t("hook.placeholders")).small().weak());
+            if ui.button(t("hook.save")).clicked() {
+                app.save_config();
+            }
+        });
+}
+
+fn render_api_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("api.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(t("api.description"));
+            ui.checkbox(&mut app.config.api.enabled, t("api.enabled"));
+            ui.horizontal(|ui| {
+                ui.label(t("api.port"));
+                ui.add(egui::DragValue::new(&mut app.config.api.port).clamp_range(1024..=65535));
+            });
+            if !app.secrets.api_token.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label(t("api.token"));
+                    ui.label(egui::RichText::new(&app.secrets.api_token).monospace());
+                    if icons.small_button(ui, "📋", t("api.copy_token")).clicked() {
+                        ui.output_mut(|output| output.copied_text = app.secrets.api_token.clone());
+                    }
+                    if icons.small_button(ui, "🔄", t("api.regenerate_token")).clicked() {
+                        app.regenerate_api_token();
+                        app.restart_api();
+                    }
+                });
+            }
+            ui.label(egui::RichText::new(if app.is_api_running() {
+                tf("api.running", &[("port", &app.config.api.port)])
+            } else {
+                t("api.stopped").to_string()
+            }).small().weak());
+            if ui.button(t("api.save")).clicked() {
+                app.save_config();
+                app.restart_api();
+            }
+        });
+}
+
+fn render_scheme_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("scheme.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(t("scheme.description"));
+            if ui.button(t("scheme.register")).clicked() {
+                app.register_scheme();
+            }
+            let bookmarklet = format!(
+                "javascript:location.href='{}://download?url='+encodeURIComponent(location.href)",
+                crate::scheme::SCHEME
+            );
+            ui.horizontal(|ui| {
+                ui.label(t("scheme.bookmarklet"));
+                if icons.small_button(ui, "📋", t("scheme.copy_bookmarklet")).clicked() {
+                    ui.output_mut(|output| output.copied_text = bookmarklet.clone());
+                }
+            });
+            ui.label(egui::RichText::new(&bookmarklet).monospace().small().weak());
+        });
+}
+
+fn render_preset_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("presets.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(t("presets.description"));
+            let mut delete = None;
+            let count = app.config.presets.len();
+            egui::Grid::new("preset_grid")
+                .num_columns(4)
+                .spacing([app.config.spacing * 2.0, app.config.spacing])
+                .show(ui, |ui| {
+                    for (index, preset) in app.config.presets.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut preset.name).desired_width(80.0))
+                            .on_hover_text(t("presets.name"));
+                        ui.add(egui::TextEdit::singleline(&mut preset.format).code_editor().desired_width(180.0))
+                            .on_hover_text(t("presets.format"));
+                        ui.checkbox(&mut preset.extract_audio, t("presets.extract_audio"));
+                        if ui.add_enabled_ui(count > 1, |ui| icons.small_button(ui, "❌", t("presets.delete"))).inner.clicked() {
+                            delete = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(index) = delete {
+                app.config.presets.remove(index);
+            }
+            ui.horizontal(|ui| {
+                if icons.button(ui, "➕", t("presets.add")).clicked() {
+                    app.config.presets.push(Preset {
+                        name: tf("presets.new_name", &[("number", &(count + 1))]),
+                        ..Preset::default()
+                    });
+                }
+                if ui.button(t("presets.save")).clicked() {
+                    app.save_config();
+                }
+            });
+        });
+}
+
+fn render_profile_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("profiles.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let available = ui.available_width() - icons.width(ui, &[t("profiles.save")]);
+                text_edit_style(
+                    app.config.row_height,
+                    app.config.margin,
+                    ui,
+                    &mut app.profile_name,
+                    Some(t("profiles.name_hint")),
+                    available
+                );
+                if icons.button(ui, "💾", t("profiles.save")).clicked() {
+                    app.save_profile();
+                }
+            });
+
+            let mut delete = None;
+            for profile in &app.config.profiles {
+                ui.horizontal(|ui| {
+                    let active = profile.name == app.config.active_profile;
+                    ui.label(if active { format!("▶ {}", profile.name) } else { profile.name.clone() });
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if icons.small_button(ui, "❌", t("profiles.delete")).clicked() {
+                            delete = Some(profile.name.clone());
+                        }
+                    });
+                });
+            }
+            if let Some(name) = delete {
+                app.delete_profile(&name);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t("profiles.export")).on_hover_text(t("profiles.export_hint")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .set_file_name(&format!("{}-settings.json", env!("APP_NAME_LOWER")))
+                        .save_file()
+                    {
+                        app.export_config(&path);
+                    }
+                }
+                if ui.button(t("profiles.import")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
+                        app.import_config(&path);
+                    }
+                }
+            });
+        });
+}
+
+fn render_detected_links(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    let mut fetch = None;
+    let mut dismiss = None;
+
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.horizontal(|ui| {
+            ui.label(tn("links.detected", app.detected_links.len(), &[]));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button(t("action.clear")).clicked() {
+                    app.detected_links.clear();
+                }
+            });
+        });
+        egui::ScrollArea::vertical()
+            .id_source("detected_links")
+            .max_height(app.config.row_height * 3.0)
+            .show(ui, |ui| {
+                for (index, link) in app.detected_links.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if icons.small_button(ui, "❌", t("action.dismiss")).clicked() {
+                                dismiss = Some(index);
+                            }
+                            if icons.small_button(ui, "🔍", t("action.fetch_formats")).clicked() {
+                                fetch = Some(index);
+                            }
+                            ui.add(egui::Label::new(link).wrap(false));
+                        });
+                    });
+                }
+            });
+    });
+
+    if let Some(index) = fetch {
+        app.fetch_detected_link(index);
+    } else if let Some(index) = dismiss {
+        app.detected_links.remove(index);
+    }
+}
+
+fn render_queue(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    let now = chrono::Local::now();
+    let next_open = app.config.schedule.next_open(now);
+    let mut remove = None;
+    let mut retry = None;
+    let mut open = None;
+    let mut reveal = None;
+    let mut cancel = false;
+
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        egui::ScrollArea::vertical()
+            .id_source("queue")
+            .max_height(app.config.row_height * 4.0)
+            .show(ui, |ui| {
+                for job in &app.queue.jobs {
+                    ui.horizontal(|ui| {
+                        if icons.text_labels {
+                            ui.label(job.state.label());
+                        } else {
+                            ui.label(job.state.icon()).on_hover_text(job.state.label());
+                        }
+                        let next_run = match job.state {
+                            JobState::Queued if job.scheduled && next_open > now => next_open.format("%H:%M").to_string(),
+                            JobState::Queued => t("queue.next").to_string(),
+                            JobState::Running => match job.progress.as_ref().and_then(|progress| progress.fraction) {
+                                Some(fraction) => format!("{:.0}%", fraction * 100.0),
+                                None => t("queue.now").to_string(),
+                            },
+                            JobState::Completed | JobState::Failed | JobState::Interrupted => String::new(),
+                        };
+                        ui.label(egui::RichText::new(next_run).monospace());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if job.state != JobState::Running && icons.small_button(ui, "❌", t("queue.remove")).clicked() {
+                                remove = Some(job.id);
+                            }
+                            if job.state == JobState::Running && icons.small_button(ui, "⏹", t("queue.cancel")).clicked() {
+                                cancel = true;
+                            }
+                            if let Some(file) = job.files.last() {
+                                if icons.small_button(ui, "📂", t("files.reveal")).clicked() {
+                                    reveal = Some(file.path.clone());
+                                }
+                                if icons.small_button(ui, "📄", t("files.open")).clicked() {
+                                    open = Some(file.path.clone());
+                                }
+                            }
+                            if matches!(job.state, JobState::Failed | JobState::Interrupted)
+                                && icons.small_button(ui, "🔄", t("queue.retry")).clicked()
+                            {
+                                retry = Some(job.id);
+                            }
+                            let label = ui.add(egui::Label::new(&job.url).wrap(false));
+                            if !job.error.is_empty() {
+                                label.on_hover_text(&job.error);
+                            }
+                        });
+                    });
+                }
+            });
+
+        if app.queue.jobs.iter().any(|job| !matches!(job.state, JobState::Queued | JobState::Running))
+            && ui.small_button(t("queue.clear_finished")).clicked()
+        {
+            app.clear_finished_jobs();
+        }
+    });
+
+    if let Some(id) = remove {
+        app.remove_job(id);
+    }
+    if cancel {
+        app.cancel_running_download();
+    }
+    if let Some(id) = retry {
+        app.retry_job(id);
+    }
+    if let Some(path) = open {
+        app.open_file(&path);
+    }
+    if let Some(path) = reveal {
+        app.reveal_file(&path);
+    }
+}
+
+fn render_file_actions(app: &mut YtDlpApp, ui: &mut egui::Ui, file: &DownloadedFile) {
+    let icons = IconStyle::new(&app.config);
+    ui.horizontal(|ui| {
+        if icons.button(ui, "📄", t("files.open")).clicked() {
+            app.open_file(&file.path);
+        }
+        if icons.button(ui, "📂", t("files.reveal")).clicked() {
+            app.reveal_file(&file.path);
+        }
+        ui.add(egui::Label::new(egui::RichText::new(&file.title).weak()).wrap(false))
+            .on_hover_text(&file.path);
+    });
+}
+
+fn render_config_errors(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    ui.group(|ui| {
+        ui.set_width(ui.available_width());
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(t("config.errors")).color(ui.visuals().error_fg_color));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if icons.small_button(ui, "❌", t("action.dismiss")).clicked() {
+                    app.config_errors.clear();
+                }
+            });
+        });
+        for error in &app.config_errors {
+            ui.label(error);
+        }
+    });
+}
+
+fn render_recovery_prompt(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let icons = IconStyle::new(&app.config);
+    egui::Window::new(t("recovery.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(tn("recovery.message", app.queue.jobs.iter().filter(|job| job.is_unfinished()).count(), &[]));
+            ui.add_space(app.config.padding);
+            ui.horizontal(|ui| {
+                if ui.button(icons.plain(t("recovery.resume"))).clicked() {
+                    app.recover_jobs(true);
+                }
+                if ui.button(icons.plain(t("recovery.discard"))).clicked() {
+                    app.recover_jobs(false);
+                }
+            });
+        });
+}
+
+/// Asked before a shortcut cancels, since Escape is easily pressed by accident.
+fn render_cancel_prompt(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let icons = IconStyle::new(&app.config);
+    egui::Window::new(t("confirm_cancel.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(t("confirm_cancel.message"));
+            ui.add_space(app.config.padding);
+            ui.horizontal(|ui| {
+                if ui.button(icons.plain(t("confirm_cancel.stop"))).clicked() {
+                    app.cancel_running_download();
+                    app.confirm_cancel = false;
+                }
+                if ui.button(icons.plain(t("confirm_cancel.keep"))).clicked() {
+                    app.confirm_cancel = false;
+                }
+            });
+        });
+}
+
+fn render_network_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("network.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let network = &mut app.config.network;
+
+            ui.horizontal(|ui| {
+                ui.label(t("network.proxy"));
+                let width = ui.available_width();
+                text_edit_style(
+                    app.config.row_height,
+                    app.config.margin,
+                    ui,
+                    &mut network.proxy,
+                    Some(t("network.proxy_hint")),
+                    width
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(t("network.limit_rate"));
+                let width = ui.available_width();
+                text_edit_style(
+                    app.config.row_height,
+                    app.config.margin,
+                    ui,
+                    &mut network.limit_rate,
+                    Some(t("network.limit_rate_hint")),
+                    width
+                );
+            });
+
+            egui::Grid::new("network_grid")
+                .num_columns(2)
+                .spacing([app.config.spacing * 2.0, app.config.spacing])
+                .show(ui, |ui| {
+                    ui.label(t("network.retries"));
+                    ui.add(egui::DragValue::new(&mut network.retries).clamp_range(0..=100));
+                    ui.end_row();
+
+                    ui.label(t("network.fragment_retries"));
+                    ui.add(egui::DragValue::new(&mut network.fragment_retries).clamp_range(0..=100));
+                    ui.end_row();
+
+                    ui.label(t("network.socket_timeout"));
+                    ui.add(egui::DragValue::new(&mut network.socket_timeout).clamp_range(0..=600).suffix(" s"))
+                        .on_hover_text(t("network.socket_timeout_hint"));
+                    ui.end_row();
+
+                    ui.label(t("network.concurrent_fragments"));
+                    ui.add(egui::DragValue::new(&mut network.concurrent_fragments).clamp_range(1..=16));
+                    ui.end_row();
+
+                    ui.label(t("network.ip_version"));
+                    egui::ComboBox::from_id_source("ip_version")
+                        .selected_text(network.ip_version.label())
+                        .show_ui(ui, |ui| {
+                            for option in IpVersion::ALL {
+                                ui.selectable_value(&mut network.ip_version, option, option.label());
+                            }
+                        });
+                    ui.end_row();
+                });
+
+            ui.checkbox(&mut network.check_certificates, t("network.check_certificates"));
+
+            ui.horizontal(|ui| {
+                ui.label(t("network.info_cache"));
+                ui.add(egui::DragValue::new(&mut app.config.info_cache_minutes).clamp_range(0..=crate::info_cache::MAX_TTL_MINUTES).suffix(" min"))
+                    .on_hover_text(t("network.info_cache_hint"));
+                if ui.small_button(t("network.clear_info_cache")).clicked() {
+                    app.clear_info_cache();
+                }
+            });
+
+            if ui.button(t("network.save")).clicked() {
+                app.save_config();
+            }
+        });
+}
+
+fn render_auth_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("auth.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut remove = None;
+
+            for (index, profile) in app.config.auth_profiles.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    // While the site is being edited, its password stays under the
+                    // original name and moves once editing ends.
+                    let original_site_id = ui.id().with("original_site");
+                    let secret_site = ui
+                        .data(|data| data.get_temp::<String>(original_site_id))
+                        .unwrap_or_else(|| profile.site.clone());
+
+                    ui.group(|ui| {
+                        ui.set_width(ui.available_width());
+
+                        ui.horizontal(|ui| {
+                            ui.label(t("auth.site"));
+                            let available = ui.available_width() - icons.width(ui, &[t("auth.remove")]);
+                            let old_site = profile.site.clone();
+                            let response = text_edit_style(
+                                app.config.row_height,
+                                app.config.margin,
+                                ui,
+                                &mut profile.site,
+                                Some(t("auth.site_hint")),
+                                available
+                            );
+                            if response.gained_focus() {
+                                ui.data_mut(|data| data.insert_temp(original_site_id, old_site));
+                            }
+                            if response.lost_focus() {
+                                ui.data_mut(|data| data.remove::<String>(original_site_id));
+                                app.secrets.rename(&secret_site, &profile.site);
+                            }
+                            if icons.button(ui, "❌", t("auth.remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label(t("auth.cookies"));
+                            let available = ui.available_width() - icons.width(ui, &[t("action.browse")]);
+                            text_edit_style(
+                                app.config.row_height,
+                                app.config.margin,
+                                ui,
+                                &mut profile.cookies_file,
+                                Some(t("auth.cookies_hint")),
+                                available
+                            );
+                            if icons.button(ui, "📂", t("action.browse")).clicked() {
+                                if let Some(path) = rfd::FileDialog::new().add_filter("cookies", &["txt"]).pick_file() {
+                                    profile.cookies_file = path.to_string_lossy().to_string();
+                                }
+                            }
+                        });
+
+                        ui.checkbox(&mut profile.use_netrc, t("auth.netrc"));
+                        ui.add_enabled_ui(!profile.use_netrc, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t("auth.username"));
+                                let width = ui.available_width();
+                                text_edit_style(
+                                    app.config.row_height,
+                                    app.config.margin,
+                                    ui,
+                                    &mut profile.username,
+                                    Some(t("auth.username_hint")),
+                                    width
+                                );
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label(t("auth.password"));
+                                let width = ui.available_width();
+                                ui.add_sized(
+                                    egui::Vec2::new(width, app.config.row_height),
+                                    egui::TextEdit::singleline(app.secrets.password_mut(&secret_site))
+                                        .password(true)
+                                        .margin(egui::vec2(app.config.margin, 0.0))
+                                        .hint_text(t("auth.password_hint"))
+                                        .vertical_align(egui::Align::Center)
+                                );
+                            });
+                        });
+                        ui.checkbox(&mut profile.twofactor, t("auth.twofactor"));
+                    });
+                });
+            }
+
+            if let Some(index) = remove {
+                let profile = app.config.auth_profiles.remove(index);
+                app.secrets.remove(&profile.site);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(t("auth.add")).clicked() {
+                    app.config.auth_profiles.push(Default::default());
+                }
+                if ui.button(t("auth.save")).clicked() {
+                    app.save_config();
+                    app.save_secrets();
+                }
+            });
+        });
+}
+
+/// Runs actions whose shortcut was pressed this frame. While the palette is
+/// open it owns the keyboard, except for the binding that toggles it.
+fn handle_shortcuts(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let keyboard_free = !ctx.wants_keyboard_input();
+    let mut triggered = Vec::new();
+
+    ctx.input_mut(|input| {
+        for binding in &app.config.shortcuts {
+            let Ok(Some(trigger)) = Trigger::parse(&binding.keys) else {
+                continue;
+            };
+            if app.show_palette && binding.action != Action::CommandPalette {
+                continue;
+            }
+            // A focused text field handles paste itself.
+            let needs_free_keyboard = trigger.needs_free_keyboard() || binding.action == Action::PasteAndFetch;
+            if !app.show_palette && needs_free_keyboard && !keyboard_free {
+                continue;
+            }
+            if trigger.consume(input) {
+                triggered.push(binding.action);
+            }
+        }
+    });
+
+    for action in triggered {
+        app.run_action(action);
+    }
+}
+
+fn shortcut_keys(app: &YtDlpApp, action: Action) -> &str {
+    app.config.shortcuts
+        .iter()
+        .find(|binding| binding.action == action)
+        .map_or("", |binding| binding.keys.as_str())
+}
+
+fn render_command_palette(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let query = app.palette_query.to_lowercase();
+    let actions: Vec<Action> = Action::ALL
+        .into_iter()
+        .filter(|action| *action != Action::CommandPalette && action.label().to_lowercase().contains(&query))
+        .collect();
+    app.palette_index = app.palette_index.min(actions.len().saturating_sub(1));
+
+    let mut run = None;
+    ctx.input_mut(|input| {
+        if input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+            app.palette_index = (app.palette_index + 1).min(actions.len().saturating_sub(1));
+        }
+        if input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+            app.palette_index = app.palette_index.saturating_sub(1);
+        }
+        if input.consume_key(egui::Modifiers::NONE, egui::Key::Enter) {
+            run = actions.get(app.palette_index).copied();
+        }
+        if input.consume_key(egui::Modifiers::NONE, egui::Key::Escape) {
+            app.show_palette = false;
+        }
+    });
+
+    egui::Window::new(t("palette.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, app.config.row_height * 2.0))
+        .show(ctx, |ui| {
+            let width = ui.available_width().max(280.0);
+            let response = text_edit_style(
+                app.config.row_height,
+                app.config.margin,
+                ui,
+                &mut app.palette_query,
+                Some(t("palette.hint")),
+                width
+            );
+            response.request_focus();
+            if response.changed() {
+                app.palette_index = 0;
+            }
+
+            for (index, action) in actions.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let highlighted = index == app.palette_index;
+                    let label = if highlighted { format!("▶ {}", action.label()) } else { action.label().to_string() };
+                    if ui.selectable_label(highlighted, label).clicked() {
+                        run = Some(*action);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(egui::RichText::new(shortcut_keys(app, *action)).monospace().weak());
+                    });
+                });
+            }
+        });
+
+    if let Some(action) = run {
+        app.show_palette = false;
+        app.run_action(action);
+    }
+}
+
+fn render_shortcut_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("shortcuts.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            egui::Grid::new("shortcuts_grid")
+                .num_columns(2)
+                .spacing([app.config.spacing * 2.0, app.config.spacing])
+                .show(ui, |ui| {
+                    for binding in &mut app.config.shortcuts {
+                        ui.label(binding.action.label());
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut binding.keys)
+                                .hint_text(t("shortcuts.unbound"))
+                                .desired_width(140.0)
+                        );
+                        if let Err(e) = Trigger::parse(&binding.keys) {
+                            response.on_hover_text(egui::RichText::new(e).color(ui.visuals().error_fg_color));
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                if ui.button(t("shortcuts.reset")).clicked() {
+                    app.config.shortcuts = default_bindings();
+                }
+                if ui.button(t("shortcuts.save")).clicked() {
+                    app.save_config();
+                }
+            });
+        });
+}
+
+fn handle_dropped_files(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+    if !dropped.is_empty() {
+        let mut links: Vec<String> = Vec::new();
+        for file in &dropped {
+            for link in links_from_dropped(file) {
+                if !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+        }
+        app.open_links(links);
+    }
+
+    if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_target")));
+        let rect = ctx.screen_rect();
+        painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(192));
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            t("drop.hint"),
+            egui::FontId::proportional(app.config.row_height),
+            egui::Color32::WHITE,
+        );
+    }
+}
+
+/// Rebuilds the egui style when the theme settings or the system theme change.
+pub fn apply_theme(app: &mut YtDlpApp, ctx: &egui::Context, system_theme: Option<eframe::Theme>) {
+    let settings = ThemeSettings {
+        mode: app.config.theme,
+        accent: app.config.accent_color,
+        font_scale: app.config.font_scale,
+        high_contrast: app.config.high_contrast,
+        system_theme,
+    };
+    if app.applied_theme != Some(settings) {
+        settings.apply(ctx);
+        app.applied_theme = Some(settings);
+    }
+}
+
 pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
+    let icons = IconStyle::new(&app.config);
+    handle_dropped_files(app, ctx);
+    handle_shortcuts(app, ctx);
+
+    if app.show_recovery {
+        render_recovery_prompt(app, ctx);
+    }
+    if app.show_palette {
+        render_command_palette(app, ctx);
+    }
+    // The download may have ended while the prompt was open.
+    app.confirm_cancel &= app.is_downloading();
+    if app.confirm_cancel {
+        render_cancel_prompt(app, ctx);
+    }
+
     egui::CentralPanel::default().show(ctx, |ui| {
         ui.spacing_mut().item_spacing = egui::vec2(app.config.spacing, app.config.spacing);
         ui.spacing_mut().window_margin = egui::style::Margin::same(app.config.margin);
@@ -107,18 +1188,24 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
             ui.group(|ui| {
                 ui.set_width(ui.available_width());
                 
-                egui::CollapsingHeader::new("📝 UI Settings")
+                egui::CollapsingHeader::new(icons.plain(t("settings.ui")))
                     .default_open(false)
                     .show(ui, |ui| {
                         ui.columns(2, |cols| {
                             cols[0].vertical(|ui| {
                                 ui.set_width(140.0);
                                 ui.vertical_centered_justified(|ui| {
-                                    ui.label("Row Height:");
-                                    ui.label("Spacing:");
-                                    ui.label("Margin:");
-                                    ui.label("Padding:");
-                                    ui.label("Icon Size:");
+                                    ui.label(t("settings.row_height"));
+                                    ui.label(t("settings.spacing"));
+                                    ui.label(t("settings.margin"));
+                                    ui.label(t("settings.padding"));
+                                    ui.label(t("settings.icon_size"));
+                                    ui.label(t("settings.theme"));
+                                    ui.label(t("settings.accent"));
+                                    ui.label(t("settings.font_scale"));
+                                    ui.label(t("settings.language"));
+                                    ui.label(t("settings.text_labels"));
+                                    ui.label(t("settings.high_contrast"));
                                 });
                             });
 
@@ -131,8 +1218,30 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
                                 ui.add(egui::Slider::new(&mut app.config.margin, 0.0..=20.0).text("px"));
                                 ui.add(egui::Slider::new(&mut app.config.padding, 0.0..=20.0).text("px"));
                                 ui.add(egui::Slider::new(&mut app.config.icon_button_size, 20.0..=40.0).text("px"));
+                                egui::ComboBox::from_id_source("theme_mode")
+                                    .selected_text(app.config.theme.label())
+                                    .show_ui(ui, |ui| {
+                                        for option in ThemeMode::ALL {
+                                            ui.selectable_value(&mut app.config.theme, option, option.label());
+                                        }
+                                    });
+                                ui.color_edit_button_srgb(&mut app.config.accent_color);
+                                ui.add(egui::Slider::new(&mut app.config.font_scale, 0.75..=2.0).text("×"));
+                                let language = app.config.language;
+                                egui::ComboBox::from_id_source("language")
+                                    .selected_text(app.config.language.label())
+                                    .show_ui(ui, |ui| {
+                                        for option in Language::ALL {
+                                            ui.selectable_value(&mut app.config.language, option, option.label());
+                                        }
+                                    });
+                                if app.config.language != language {
+                                    i18n::set_language(app.config.language);
+                                }
+                                ui.checkbox(&mut app.config.text_labels, t("settings.text_labels_hint"));
+                                ui.checkbox(&mut app.config.high_contrast, t("settings.high_contrast_hint"));
                                 
-                                if ui.button("Save UI Settings").clicked() {
+                                if ui.button(t("settings.save")).clicked() {
                                     app.save_config();
                                 }
                             });
@@ -141,49 +1250,91 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
 
                 ui.add_space(app.config.padding);
 
+                render_column_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
                 // Path settings
-                egui::CollapsingHeader::new("📂 Paths")
+                egui::CollapsingHeader::new(icons.plain(t("paths.title")))
                     .default_open(true)
                     .show(ui, |ui| {
+                        ui.label(egui::RichText::new(format!(
+                            "{}{}",
+                            tf("paths.config", &[("path", &Config::get_config_path().display())]),
+                            if Config::is_portable() { t("paths.portable") } else { "" }
+                        )).small().weak());
+
                         // YT-DLP Path
                         ui.horizontal(|ui| {
-                            ui.label("YT-DLP Path:");
-                            let available = ui.available_width() - (app.config.icon_button_size + app.config.spacing);
-                            text_edit_style(
+                            ui.label(t("paths.yt_dlp"));
+                            let available = ui.available_width() - icons.width(ui, &[t("action.browse")]);
+                            let response = text_edit_style(
                                 app.config.row_height,
                                 app.config.margin,
                                 ui,
                                 &mut app.yt_dlp_path,
-                                Some("Path to yt-dlp executable"),
+                                Some(t("paths.yt_dlp_hint")),
                                 available
                             );
-                            if ui.add_sized(
-                                egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-                                egui::Button::new("📂")
-                            ).clicked() {
+                            if response.lost_focus() {
+                                app.save_config();
+                                app.check_yt_dlp();
+                            }
+                            if icons.button(ui, "📂", t("action.browse")).clicked() {
                                 if let Some(path) = rfd::FileDialog::new().pick_file() {
                                     app.yt_dlp_path = path.to_string_lossy().to_string();
                                     app.save_config();
+                                    app.check_yt_dlp();
+                                }
+                            }
+                        });
+
+                        render_yt_dlp_status(app, ui);
+
+                        // FFmpeg Path
+                        ui.horizontal(|ui| {
+                            ui.label(t("paths.ffmpeg"));
+                            let available = ui.available_width() - icons.width(ui, &[t("action.browse")]);
+                            let response = text_edit_style(
+                                app.config.row_height,
+                                app.config.margin,
+                                ui,
+                                &mut app.config.ffmpeg_path,
+                                Some(t("paths.ffmpeg_hint")),
+                                available
+                            );
+                            if response.lost_focus() {
+                                app.save_config();
+                                app.check_ffmpeg();
+                            }
+                            if icons.button(ui, "📂", t("action.browse")).clicked() {
+                                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                                    app.config.ffmpeg_path = path.to_string_lossy().to_string();
+                                    app.save_config();
+                                    app.check_ffmpeg();
                                 }
                             }
                         });
+
+                        match &app.ffmpeg_version {
+                            Some(version) => ui.label(format!("✅ ffmpeg {}", version)),
+                            None => ui.colored_label(ui.visuals().error_fg_color, format!("❌ {}", app.ffmpeg_error))
+                                .on_hover_text(t("paths.ffmpeg_missing_hint")),
+                        };
                         
                         // Download Directory
                         ui.horizontal(|ui| {
-                            ui.label("Save To:");
-                            let available = ui.available_width() - (app.config.icon_button_size + app.config.spacing);
+                            ui.label(t("paths.download_dir"));
+                            let available = ui.available_width() - icons.width(ui, &[t("action.browse")]);
                             text_edit_style(
                                 app.config.row_height,
                                 app.config.margin,
                                 ui,
                                 &mut app.download_dir,
-                                Some("Download destination folder"),
+                                Some(t("paths.download_dir_hint")),
                                 available
                             );
-                            if ui.add_sized(
-                                egui::Vec2::new(app.config.icon_button_size, app.config.icon_button_size),
-                                egui::Button::new("📂")
-                            ).clicked() {
+                            if icons.button(ui, "📂", t("action.browse")).clicked() {
                                 if let Some(path) = rfd::FileDialog::new().pick_folder() {
                                     app.download_dir = path.to_string_lossy().to_string();
                                     app.save_config();
@@ -191,10 +1342,60 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
                             }
                         });
                     });
+
+                ui.add_space(app.config.padding);
+
+                render_auth_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_network_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_schedule_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_clipboard_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_notification_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_post_download_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_api_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_scheme_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_preset_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_profile_settings(app, ui);
+
+                ui.add_space(app.config.padding);
+
+                render_shortcut_settings(app, ui);
             });
             ui.add_space(app.config.padding);
         }
 
+        // Config problems
+        if !app.config_errors.is_empty() {
+            render_config_errors(app, ui);
+            ui.add_space(app.config.padding);
+        }
+
         // URL Input
         render_url_input(app, ui);
 
@@ -203,10 +1404,28 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
 
         // Status Message
         if !app.status.is_empty() {
-            let status_color = get_status_color(&app.status);
+            let status_color = get_status_color(app.status_kind, ui.visuals());
             ui.vertical_centered(|ui| {
                 ui.colored_label(status_color, &app.status);
+                if !app.last_command.is_empty() {
+                    ui.label(egui::RichText::new(&app.last_command).monospace().small().weak());
+                }
             });
+            if let Some(file) = app.last_download.clone() {
+                render_file_actions(app, ui, &file);
+            }
+            ui.add_space(app.config.spacing);
+        }
+
+        // Detected links
+        if !app.detected_links.is_empty() {
+            render_detected_links(app, ui);
+            ui.add_space(app.config.spacing);
+        }
+
+        // Queue
+        if !app.queue.jobs.is_empty() {
+            render_queue(app, ui);
             ui.add_space(app.config.spacing);
         }
 
@@ -215,98 +1434,32 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
             ui.group(|ui| {
                 ui.set_width(ui.available_width());
                 ui.vertical(|ui| {
-                    // Sticky header
-                    let total_width = ui.available_width() - (app.config.spacing * 12.0);  // Account for grid spacing
-                    let col_widths = [
-                        0.04, // Select (smaller)
-                        0.08, // ID (larger)
-                        0.16, // Type
-                        0.2,  // Resolution
-                        0.2,  // Video
-                        0.2   // Audio
-                    ];
-                    
-                    egui::ScrollArea::horizontal().show(ui, |ui| {
-                        egui::Grid::new("formats_header")
-                            .num_columns(6)
-                            .spacing([app.config.spacing * 2.0, app.config.spacing])
-                            .show(ui, |ui| {
-                                for (i, width) in col_widths.iter().enumerate() {
-                                    let column_width = total_width * width;
-                                    match i {
-                                        0 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("Select")),
-                                        1 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("ID")),
-                                        2 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("Type")),
-                                        3 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("Resolution")),
-                                        4 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("Video")),
-                                        5 => ui.add_sized([column_width, app.config.row_height], egui::Label::new("Audio")),
-                                        _ => unreachable!(),
-                                    };
-                                }
-                                ui.end_row();
-                            });
-
-                        let scroll_height = ui.available_height() - if app.selected_format.is_some() { app.config.row_height + app.config.padding } else { 0.0 };
-                        egui::ScrollArea::vertical()
-                            .max_height(scroll_height)
-                            .show(ui, |ui| {
-                                egui::Grid::new("formats_grid")
-                                    .num_columns(6)
-                                    .striped(true)
-                                    .spacing([app.config.spacing * 2.0, app.config.spacing])
-                                    .show(ui, |ui| {
-                                        for (index, format) in app.formats.iter().enumerate() {
-                                            for (i, width) in col_widths.iter().enumerate() {
-                                                let column_width = total_width * width;
-                                                match i {
-                                                    0 => {
-                                                        if ui.add_sized([column_width, app.config.row_height], 
-                                                            egui::SelectableLabel::new(
-                                                                app.selected_format == Some(index),
-                                                                "○"
-                                                            )).clicked() {
-                                                            app.selected_format = Some(index);
-                                                        }
-                                                    },
-                                                    1 => {
-                                                        ui.add_sized([column_width, app.config.row_height],
-                                                            egui::Label::new(egui::RichText::new(&format.format_id).monospace()));
-                                                    },
-                                                    2 => {
-                                                        ui.add_sized([column_width, app.config.row_height],
-                                                            egui::Label::new(&format.ext));
-                                                    },
-                                                    3 => {
-                                                        ui.add_sized([column_width, app.config.row_height],
-                                                            egui::Label::new(format.resolution.as_deref().unwrap_or("N/A")));
-                                                    },
-                                                    4 => {
-                                                        ui.add_sized([column_width, app.config.row_height],
-                                                            egui::Label::new(short_codec(&format.vcodec)));
-                                                    },
-                                                    5 => {
-                                                        ui.add_sized([column_width, app.config.row_height],
-                                                            egui::Label::new(format.acodec.as_deref().unwrap_or("N/A")));
-                                                    },
-                                                    _ => unreachable!(),
-                                                };
-                                            }
-                                            ui.end_row();
-                                        }
-                                    });
-                            });
-                    });
+                    let extra_rows = if app.selected_format.is_some() { 3.0 } else { 0.0 };
+                    let scroll_height = ui.available_height() - (app.config.row_height + app.config.padding) * extra_rows;
+                    render_formats_table(app, ui, scroll_height);
 
                     // Download button
                     if app.selected_format.is_some() {
                         ui.add_space(app.config.padding);
+                        render_download_options(app, ui);
                         ui.horizontal(|ui| {
+                            let schedule_label = tf("formats.schedule", &[
+                                ("start", &format_minutes(app.config.schedule.window_start)),
+                                ("end", &format_minutes(app.config.schedule.window_end)),
+                            ]);
                             if ui.add_sized(
-                                egui::Vec2::new(ui.available_width(), app.config.row_height),
-                                egui::Button::new("⏬ Download")
+                                egui::Vec2::new(ui.available_width() - icons.width(ui, &[&schedule_label]), app.config.row_height),
+                                egui::Button::new(icons.plain(t("formats.download")))
                             ).clicked() {
                                 app.download_selected_format();
                             }
+                            let schedule = ui
+                                .add_enabled_ui(app.can_schedule(), |ui| icons.button(ui, "⏰", &schedule_label))
+                                .inner
+                                .on_disabled_hover_text(t("formats.schedule_twofactor"));
+                            if schedule.clicked() {
+                                app.schedule_selected_format();
+                            }
                         });
                     }
                 });
@@ -315,12 +1468,138 @@ pub fn render_ui(app: &mut YtDlpApp, ctx: &egui::Context) {
     });
 }
 
-fn get_status_color(status: &str) -> egui::Color32 {
-    if status.contains("Error") {
-        egui::Color32::RED
-    } else if status.contains("success") || status.contains("completed") {
-        egui::Color32::GREEN
-    } else {
-        egui::Color32::WHITE
+fn render_formats_table(app: &mut YtDlpApp, ui: &mut egui::Ui, max_height: f32) {
+    let visible: Vec<usize> = app.config.format_columns
+        .iter()
+        .enumerate()
+        .filter(|(_, setting)| setting.visible)
+        .map(|(index, _)| index)
+        .collect();
+    let layout: Vec<FormatColumn> = visible.iter().map(|&index| app.config.format_columns[index].column).collect();
+    let row_height = app.config.row_height;
+    let mut widths = Vec::new();
+    let mut hide = None;
+
+    // A new column set gets fresh resize state seeded from the saved widths.
+    ui.push_id(&layout, |ui| {
+        let mut table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .max_scroll_height(max_height)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center));
+        for &index in &visible {
+            table = table.column(Column::initial(app.config.format_columns[index].width).at_least(24.0).clip(true));
+        }
+
+        let formats = &app.formats;
+        let selected = &mut app.selected_format;
+        table
+            .header(row_height, |mut header| {
+                for column in &layout {
+                    header.col(|ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(column.label()).strong()).sense(egui::Sense::click()))
+                            .context_menu(|ui| {
+                                if *column != FormatColumn::Select && ui.button(t("columns.hide")).clicked() {
+                                    hide = Some(*column);
+                                    ui.close_menu();
+                                }
+                            });
+                    });
+                }
+            })
+            .body(|body| {
+                widths = body.widths().to_vec();
+                body.rows(row_height, formats.len(), |index, mut row| {
+                    let format = &formats[index];
+                    for column in &layout {
+                        row.col(|ui| match column {
+                            FormatColumn::Select => {
+                                let is_selected = *selected == Some(index);
+                                let response = ui.selectable_label(is_selected, if is_selected { "●" } else { "○" });
+                                response.widget_info(|| egui::WidgetInfo::selected(
+                                    egui::WidgetType::RadioButton,
+                                    is_selected,
+                                    tf("formats.select", &[("id", &format.format_id)]),
+                                ));
+                                if response.clicked() {
+                                    *selected = Some(index);
+                                }
+                            }
+                            FormatColumn::Id => {
+                                ui.label(egui::RichText::new(column.cell_text(format)).monospace());
+                            }
+                            _ => {
+                                ui.label(column.cell_text(format));
+                            }
+                        });
+                    }
+                });
+            });
+    });
+
+    for (&index, &width) in visible.iter().zip(&widths) {
+        let setting = &mut app.config.format_columns[index];
+        if (setting.width - width).abs() > 0.5 {
+            setting.width = width;
+            app.columns_changed = true;
+        }
     }
-}
\ No newline at end of file
+    if app.columns_changed && !ui.input(|i| i.pointer.any_down()) {
+        app.columns_changed = false;
+        app.save_config();
+    }
+    if let Some(column) = hide {
+        if let Some(setting) = app.config.format_columns.iter_mut().find(|setting| setting.column == column) {
+            setting.visible = false;
+        }
+        app.save_config();
+    }
+}
+
+fn render_column_settings(app: &mut YtDlpApp, ui: &mut egui::Ui) {
+    let icons = IconStyle::new(&app.config);
+    egui::CollapsingHeader::new(icons.plain(t("columns.title")))
+        .default_open(false)
+        .show(ui, |ui| {
+            let count = app.config.format_columns.len();
+            let mut move_up = None;
+            let mut changed = false;
+
+            for index in 0..count {
+                let setting = &mut app.config.format_columns[index];
+                ui.horizontal(|ui| {
+                    let enabled = setting.column != FormatColumn::Select;
+                    changed |= ui.add_enabled(enabled, egui::Checkbox::new(&mut setting.visible, setting.column.label())).changed();
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.add_enabled_ui(index + 1 < count, |ui| icons.small_button(ui, "⬇", t("columns.move_down"))).inner.clicked() {
+                            move_up = Some(index + 1);
+                        }
+                        if ui.add_enabled_ui(index > 0, |ui| icons.small_button(ui, "⬆", t("columns.move_up"))).inner.clicked() {
+                            move_up = Some(index);
+                        }
+                    });
+                });
+            }
+
+            if let Some(index) = move_up {
+                app.config.format_columns.swap(index - 1, index);
+                changed = true;
+            }
+            if ui.button(t("columns.reset")).clicked() {
+                app.config.format_columns = default_columns();
+                changed = true;
+            }
+            if changed {
+                app.save_config();
+            }
+        });
+}
+
+fn get_status_color(kind: StatusKind, visuals: &egui::Visuals) -> egui::Color32 {
+    match kind {
+        StatusKind::Error => visuals.error_fg_color,
+        StatusKind::Success if visuals.dark_mode => egui::Color32::GREEN,
+        StatusKind::Success => egui::Color32::from_rgb(0, 128, 0),
+        StatusKind::Info => visuals.text_color(),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::i18n::tf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(windows)]
+const YT_DLP_BINARY: &str = "yt-dlp.exe";
+#[cfg(not(windows))]
+const YT_DLP_BINARY: &str = "yt-dlp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Nightly,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Nightly];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Nightly => "nightly",
+        }
+    }
+}
+
+/// Runs `<path> --version` and returns the trimmed version string.
+pub fn probe_version(path: &str) -> Result<String, String> {
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .map_err(|e| tf("errors.cannot_run", &[("path", &path), ("error", &e)]))?;
+
+    if !output.status.success() {
+        return Err(tf("errors.exited_with", &[("path", &path), ("status", &output.status)]));
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if version.is_empty() {
+        return Err(tf("errors.no_version", &[("path", &path)]));
+    }
+    Ok(version)
+}
+
+/// Looks for an executable called `name` in every directory listed in `PATH`.
+pub fn find_on_path(name: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Validates the configured yt-dlp path, falling back to the one on `PATH`.
+/// Returns the usable path together with its version.
+pub fn resolve(configured: &str) -> Result<(String, String), String> {
+    let configured_error = if Path::new(configured).is_file() {
+        match probe_version(configured) {
+            Ok(version) => return Ok((configured.to_string(), version)),
+            Err(e) => e,
+        }
+    } else {
+        tf("errors.yt_dlp_not_found_at", &[("path", &configured)])
+    };
+
+    if let Some(found) = find_on_path(YT_DLP_BINARY) {
+        let found = found.to_string_lossy().to_string();
+        if let Ok(version) = probe_version(&found) {
+            return Ok((found, version));
+        }
+    }
+
+    Err(configured_error)
+}
+
+/// Runs yt-dlp's self-updater. `None` updates within the current channel (`-U`),
+/// otherwise switches to the given channel with `--update-to`.
+pub fn self_update(path: &str, channel: Option<UpdateChannel>) -> Result<String, String> {
+    let mut command = Command::new(path);
+    match channel {
+        Some(channel) => command.args(["--update-to", channel.as_str()]),
+        None => command.arg("-U"),
+    };
+
+    let output = command
+        .output()
+        .map_err(|e| tf("errors.cannot_run", &[("path", &path), ("error", &e)]))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(stderr.trim());
+    }
+
+    if output.status.success() {
+        Ok(text)
+    } else {
+        Err(text)
+    }
+}